@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::{Debug, Formatter};
 use std::iter;
 
@@ -9,7 +9,7 @@ use miette::{Diagnostic, Result};
 use thiserror::Error;
 
 use crate::data::attr::Attribute;
-use crate::data::expr::{compute_bounds, compute_single_bound, Expr};
+use crate::data::expr::{compute_bounds, compute_single_bound, Expr, Op};
 use crate::data::id::{AttrId, EntityId, Validity};
 use crate::data::symb::Symbol;
 use crate::data::tuple::{Tuple, TupleIter};
@@ -35,6 +35,7 @@ pub(crate) struct UnificationRA {
     parent: Box<RelAlgebra>,
     binding: Symbol,
     expr: Expr,
+    expr_bytecode: Vec<ExprByteCode>,
     is_multi: bool,
     pub(crate) to_eliminate: BTreeSet<Symbol>,
     span: SourceSpan,
@@ -45,6 +46,186 @@ pub(crate) struct UnificationRA {
 #[diagnostic(code(eval::iter_bad_entity_id))]
 struct EntityIdExpected(DataValue, #[label] SourceSpan);
 
+#[derive(Debug, Error, Diagnostic)]
+#[error("Predicate compiled to bytecode evaluated to {0:?} instead of a boolean")]
+#[diagnostic(code(eval::bytecode_pred_not_bool))]
+struct BytecodePredNotBool(DataValue);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Join type {0:?} is not supported for this attribute's join shape")]
+#[diagnostic(code(eval::unsupported_outer_join_shape))]
+#[diagnostic(help(
+    "Optional matches (`?=`) on this attribute need an indexed or ref-typed value; \
+     unindexed-value and entity-keyed joins only support left-outer matching"
+))]
+struct UnsupportedOuterJoinShape(JoinType, #[label] SourceSpan);
+
+#[derive(Debug, Clone)]
+pub(crate) enum ExprByteCode {
+    Binding { tuple_pos: usize },
+    Const { val: DataValue },
+    Apply { op: &'static Op, arity: usize },
+    JumpIfFalse { jump_to: usize },
+    Goto { jump_to: usize },
+    /// Falls back to tree-walking evaluation for an `Expr` form `expr2bytecode` doesn't lower
+    /// (anything other than `Binding`/`Const`/a plain or short-circuiting `Apply`), instead of
+    /// panicking at compile time on an otherwise-valid expression.
+    Eval { expr: Expr },
+}
+
+pub(crate) fn expr2bytecode(expr: &Expr, collector: &mut Vec<ExprByteCode>) {
+    match expr {
+        Expr::Binding { var: _, tuple_pos } => collector.push(ExprByteCode::Binding {
+            tuple_pos: tuple_pos.expect("binding index not resolved before bytecode compilation"),
+        }),
+        Expr::Const { val, .. } => collector.push(ExprByteCode::Const { val: val.clone() }),
+        Expr::Apply { op, args, .. } if op.name() == "and" => {
+            compile_short_circuit(args, true, collector)
+        }
+        Expr::Apply { op, args, .. } if op.name() == "or" => {
+            compile_short_circuit(args, false, collector)
+        }
+        Expr::Apply { op, args, .. } => {
+            for arg in args.iter() {
+                expr2bytecode(arg, collector);
+            }
+            collector.push(ExprByteCode::Apply {
+                op,
+                arity: args.len(),
+            });
+        }
+        _ => collector.push(ExprByteCode::Eval { expr: expr.clone() }),
+    }
+}
+
+fn compile_short_circuit(args: &[Expr], is_and: bool, collector: &mut Vec<ExprByteCode>) {
+    if args.len() == 1 {
+        expr2bytecode(&args[0], collector);
+        return;
+    }
+    expr2bytecode(&args[0], collector);
+    let branch_idx = collector.len();
+    collector.push(ExprByteCode::JumpIfFalse { jump_to: 0 });
+    if is_and {
+        compile_short_circuit(&args[1..], is_and, collector);
+        let goto_idx = collector.len();
+        collector.push(ExprByteCode::Goto { jump_to: 0 });
+        let short_circuit_at = collector.len();
+        collector.push(ExprByteCode::Const {
+            val: DataValue::Bool(false),
+        });
+        let end = collector.len();
+        patch_jump(collector, branch_idx, short_circuit_at);
+        patch_jump(collector, goto_idx, end);
+    } else {
+        let short_circuit_at = collector.len();
+        collector.push(ExprByteCode::Const {
+            val: DataValue::Bool(true),
+        });
+        let goto_idx = collector.len();
+        collector.push(ExprByteCode::Goto { jump_to: 0 });
+        let rest_at = collector.len();
+        compile_short_circuit(&args[1..], is_and, collector);
+        let end = collector.len();
+        patch_jump(collector, branch_idx, rest_at);
+        patch_jump(collector, goto_idx, end);
+    }
+}
+
+fn patch_jump(collector: &mut [ExprByteCode], idx: usize, target: usize) {
+    match &mut collector[idx] {
+        ExprByteCode::JumpIfFalse { jump_to } | ExprByteCode::Goto { jump_to } => {
+            *jump_to = target
+        }
+        _ => unreachable!("patch_jump called on a non-jump instruction"),
+    }
+}
+
+fn run_bytecode(bytecode: &[ExprByteCode], tuple: &Tuple, stack: &mut Vec<DataValue>) -> Result<()> {
+    stack.clear();
+    let mut pc = 0;
+    while pc < bytecode.len() {
+        match &bytecode[pc] {
+            ExprByteCode::Binding { tuple_pos } => {
+                stack.push(tuple.0[*tuple_pos].clone());
+                pc += 1;
+            }
+            ExprByteCode::Const { val } => {
+                stack.push(val.clone());
+                pc += 1;
+            }
+            ExprByteCode::Apply { op, arity } => {
+                let at = stack.len() - *arity;
+                let args = stack.split_off(at);
+                let result = op.eval(&args)?;
+                stack.push(result);
+                pc += 1;
+            }
+            ExprByteCode::JumpIfFalse { jump_to } => {
+                let cond = stack.pop().expect("bytecode VM stack underflow: compiler bug");
+                if matches!(cond, DataValue::Bool(false)) {
+                    // the jump target always pushes its own replacement value (a `Const` or
+                    // further computation), so the popped condition must not be pushed back,
+                    // or the stack would end up holding two values instead of one.
+                    pc = *jump_to;
+                } else {
+                    pc += 1;
+                }
+            }
+            ExprByteCode::Goto { jump_to } => {
+                pc = *jump_to;
+            }
+            ExprByteCode::Eval { expr } => {
+                stack.push(expr.eval(tuple)?);
+                pc += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn eval_bytecode(bytecode: &[ExprByteCode], tuple: &Tuple) -> Result<DataValue> {
+    let mut stack = Vec::with_capacity(bytecode.len());
+    eval_bytecode_with_stack(bytecode, tuple, &mut stack)
+}
+
+/// Same as [`eval_bytecode`], but reuses `stack` across calls instead of allocating a fresh
+/// one each time. `stack` is cleared (not reallocated) by [`run_bytecode`] on entry.
+fn eval_bytecode_with_stack(
+    bytecode: &[ExprByteCode],
+    tuple: &Tuple,
+    stack: &mut Vec<DataValue>,
+) -> Result<DataValue> {
+    run_bytecode(bytecode, tuple, stack)?;
+    Ok(stack
+        .pop()
+        .expect("bytecode VM finished with an empty stack: compiler bug"))
+}
+
+pub(crate) fn eval_bytecode_pred(bytecode: &[ExprByteCode], tuple: &Tuple) -> Result<bool> {
+    let mut stack = Vec::with_capacity(bytecode.len());
+    eval_bytecode_pred_with_stack(bytecode, tuple, &mut stack)
+}
+
+fn eval_bytecode_pred_with_stack(
+    bytecode: &[ExprByteCode],
+    tuple: &Tuple,
+    stack: &mut Vec<DataValue>,
+) -> Result<bool> {
+    match eval_bytecode_with_stack(bytecode, tuple, stack)? {
+        DataValue::Bool(b) => Ok(b),
+        v => Err(BytecodePredNotBool(v).into()),
+    }
+}
+
+/// Pads an unmatched left tuple in [`NoIndexMergeJoin`] with the single `DataValue::Null` column
+/// a match would have contributed (the matched entity id), so `LeftOuter`/`FullOuter` rows have
+/// the same width as matched ones.
+fn pad_unmatched_left(mut t: Tuple, eliminate_indices: &BTreeSet<usize>) -> Tuple {
+    t.0.push(DataValue::Null);
+    eliminate_from_tuple(t, eliminate_indices)
+}
+
 fn eliminate_from_tuple(mut ret: Tuple, eliminate_indices: &BTreeSet<usize>) -> Tuple {
     if !eliminate_indices.is_empty() {
         ret = Tuple(
@@ -73,7 +254,10 @@ impl UnificationRA {
             .enumerate()
             .map(|(a, b)| (b, a))
             .collect();
-        self.expr.fill_binding_indices(&parent_bindings)
+        self.expr.fill_binding_indices(&parent_bindings)?;
+        self.expr_bytecode.clear();
+        expr2bytecode(&self.expr, &mut self.expr_bytecode);
+        Ok(())
     }
     pub(crate) fn do_eliminate_temp_vars(&mut self, used: &BTreeSet<Symbol>) -> Result<()> {
         for binding in self.parent.bindings_before_eliminate() {
@@ -97,11 +281,12 @@ impl UnificationRA {
         bindings.push(self.binding.clone());
         let eliminate_indices = get_eliminate_indices(&bindings, &self.to_eliminate);
         Ok(if self.is_multi {
+            let mut stack = vec![];
             let it = self
                 .parent
                 .iter(tx, epoch, use_delta)?
                 .map_ok(move |tuple| -> Result<Vec<Tuple>> {
-                    let result_list = self.expr.eval(&tuple)?;
+                    let result_list = eval_bytecode_with_stack(&self.expr_bytecode, &tuple, &mut stack)?;
                     let result_list = result_list.get_list().ok_or_else(|| {
                         #[derive(Debug, Error, Diagnostic)]
                         #[error("Invalid spread unification")]
@@ -125,11 +310,12 @@ impl UnificationRA {
                 .flatten_ok();
             Box::new(it)
         } else {
+            let mut stack = vec![];
             Box::new(
                 self.parent
                     .iter(tx, epoch, use_delta)?
                     .map_ok(move |tuple| -> Result<Tuple> {
-                        let result = self.expr.eval(&tuple)?;
+                        let result = eval_bytecode_with_stack(&self.expr_bytecode, &tuple, &mut stack)?;
                         let mut ret = tuple.0;
                         ret.push(result);
                         let ret = Tuple(ret);
@@ -145,6 +331,7 @@ impl UnificationRA {
 pub(crate) struct FilteredRA {
     parent: Box<RelAlgebra>,
     pred: Vec<Expr>,
+    pred_bytecode: Vec<Vec<ExprByteCode>>,
     pub(crate) to_eliminate: BTreeSet<Symbol>,
 }
 
@@ -174,6 +361,15 @@ impl FilteredRA {
         for e in self.pred.iter_mut() {
             e.fill_binding_indices(&parent_bindings)?;
         }
+        self.pred_bytecode = self
+            .pred
+            .iter()
+            .map(|e| {
+                let mut collector = vec![];
+                expr2bytecode(e, &mut collector);
+                collector
+            })
+            .collect();
         Ok(())
     }
     fn iter<'a>(
@@ -184,13 +380,14 @@ impl FilteredRA {
     ) -> Result<TupleIter<'a>> {
         let bindings = self.parent.bindings_after_eliminate();
         let eliminate_indices = get_eliminate_indices(&bindings, &self.to_eliminate);
+        let mut stack = vec![];
         Ok(Box::new(
             self.parent
                 .iter(tx, epoch, use_delta)?
                 .filter_map(move |tuple| match tuple {
                     Ok(t) => {
-                        for p in self.pred.iter() {
-                            match p.eval_pred(&t) {
+                        for p in self.pred_bytecode.iter() {
+                            match eval_bytecode_pred_with_stack(p, &t, &mut stack) {
                                 Ok(false) => return None,
                                 Err(e) => return Some(Err(e)),
                                 Ok(true) => {}
@@ -250,6 +447,7 @@ impl Debug for RelAlgebra {
                 .field(&bindings)
                 .field(&r.storage.name)
                 .field(&r.filters)
+                .field(&r.valid_at)
                 .finish(),
             RelAlgebra::Join(r) => {
                 if r.left.is_unit() {
@@ -297,11 +495,68 @@ impl RelAlgebra {
         match self {
             RelAlgebra::Triple(t) => Some(&mut t.filters),
             RelAlgebra::Derived(d) => Some(&mut d.filters),
+            RelAlgebra::Relation(r) => Some(&mut r.filters),
             RelAlgebra::Join(j) => j.right.get_filters(),
             RelAlgebra::Filter(f) => Some(&mut f.pred),
             _ => None,
         }
     }
+    /// Sinks predicates down to the lowest node whose bindings already cover them, and swaps a
+    /// join's children when the left one is a leaf atom (fixed rows, triple, derived or stored
+    /// relation) and the right one is not, so the leaf becomes the side probed for each left
+    /// tuple. No selectivity or cost estimate is used to order joins. Safe to
+    /// run on any tree: a predicate that cannot be sunk stays in its `FilteredRA`, and a join
+    /// that cannot be reordered is left untouched. Must run before `fill_normal_binding_indices`.
+    pub(crate) fn optimize(mut self) -> Self {
+        self.reorder_joins();
+        self.push_down_filters();
+        self
+    }
+    fn reorder_joins(&mut self) {
+        match self {
+            RelAlgebra::Join(j) => {
+                j.left.reorder_joins();
+                j.right.reorder_joins();
+                if prefers_right_side(&j.left) && !prefers_right_side(&j.right) {
+                    std::mem::swap(&mut j.left, &mut j.right);
+                    std::mem::swap(&mut j.joiner.left_keys, &mut j.joiner.right_keys);
+                }
+            }
+            RelAlgebra::NegJoin(j) => j.left.reorder_joins(),
+            RelAlgebra::Reorder(r) => r.relation.reorder_joins(),
+            RelAlgebra::Filter(f) => f.parent.reorder_joins(),
+            RelAlgebra::Unification(u) => u.parent.reorder_joins(),
+            _ => {}
+        }
+    }
+    fn push_down_filters(&mut self) {
+        match self {
+            RelAlgebra::Filter(f) => {
+                f.parent.push_down_filters();
+                let mut remaining = vec![];
+                for pred in f.pred.drain(..) {
+                    let bindings: BTreeSet<Symbol> = pred.bindings().into_iter().collect();
+                    if !sink_filter(&mut f.parent, &bindings, &pred) {
+                        remaining.push(pred);
+                    }
+                }
+                f.pred = remaining;
+                f.pred_bytecode.clear();
+                if f.pred.is_empty() {
+                    let parent = std::mem::replace(&mut f.parent, Box::new(RelAlgebra::unit()));
+                    *self = *parent;
+                }
+            }
+            RelAlgebra::Join(j) => {
+                j.left.push_down_filters();
+                j.right.push_down_filters();
+            }
+            RelAlgebra::NegJoin(j) => j.left.push_down_filters(),
+            RelAlgebra::Reorder(r) => r.relation.push_down_filters(),
+            RelAlgebra::Unification(u) => u.parent.push_down_filters(),
+            _ => {}
+        }
+    }
     pub(crate) fn fill_normal_binding_indices(&mut self) -> Result<()> {
         match self {
             RelAlgebra::Fixed(_) => {}
@@ -372,13 +627,27 @@ impl RelAlgebra {
             bindings,
             storage,
             filters: vec![],
+            filters_bytecode: vec![],
         })
     }
-    pub(crate) fn relation(bindings: Vec<Symbol>, storage: RelationMetadata) -> Self {
+    /// `key_arity` is the number of leading columns in `bindings`/`storage`'s rows that make up
+    /// the relation's actual primary key, *not* counting the validity column or any value
+    /// columns that follow it -- it is what [`RelationRA`]'s as-of scan uses to find the
+    /// validity column and to dedup versions of the same logical row, instead of assuming the
+    /// validity column is the row's last one.
+    pub(crate) fn relation(
+        bindings: Vec<Symbol>,
+        storage: RelationMetadata,
+        valid_at: Option<Validity>,
+        key_arity: usize,
+    ) -> Self {
         Self::Relation(RelationRA {
             bindings,
             storage,
             filters: vec![],
+            filters_bytecode: vec![],
+            valid_at,
+            key_arity,
         })
     }
     pub(crate) fn triple(
@@ -393,7 +662,12 @@ impl RelAlgebra {
             vld,
             bindings: [e_binding, v_binding],
             filters: vec![],
+            filters_bytecode: vec![],
             span,
+            join_spill_threshold: DEFAULT_JOIN_SPILL_THRESHOLD,
+            join_type: JoinType::Inner,
+            no_index_cache: Default::default(),
+            left_spill: Default::default(),
         })
     }
     pub(crate) fn reorder(self, new_order: Vec<Symbol>) -> Self {
@@ -402,10 +676,34 @@ impl RelAlgebra {
             new_order,
         })
     }
+    /// Makes an optional match (`?=`) out of a just-built [`RelAlgebra::Triple`]: unmatched left
+    /// (resp. right) rows are kept with the columns they would have gained padded with
+    /// `DataValue::Null` instead of being dropped. Only joins on exactly one column honor it, and
+    /// joins on the entity column or on an unindexed value column support `LeftOuter` only (the
+    /// other outer types are rejected with an error); joins binding both columns, or neither,
+    /// ignore it and behave as inner joins. No-op on any other variant, since only
+    /// [`TripleRA`] carries a [`JoinType`].
+    pub(crate) fn with_join_type(mut self, join_type: JoinType) -> Self {
+        if let RelAlgebra::Triple(t) = &mut self {
+            t.join_type = join_type;
+        }
+        self
+    }
+    /// Overrides the row budget [`TripleRA::spilling_no_index_join`] buffers before sorting and
+    /// spilling a run to disk; defaults to [`DEFAULT_JOIN_SPILL_THRESHOLD`]. Exposed so tests can
+    /// force the spill path with small inputs instead of needing to construct >100k rows. No-op
+    /// on any variant other than [`RelAlgebra::Triple`].
+    pub(crate) fn with_join_spill_threshold(mut self, threshold: usize) -> Self {
+        if let RelAlgebra::Triple(t) = &mut self {
+            t.join_spill_threshold = threshold;
+        }
+        self
+    }
     pub(crate) fn filter(self, filter: Expr) -> Self {
         RelAlgebra::Filter(FilteredRA {
             parent: Box::new(self),
             pred: vec![filter],
+            pred_bytecode: vec![],
             to_eliminate: Default::default(),
         })
     }
@@ -420,6 +718,7 @@ impl RelAlgebra {
             parent: Box::new(self),
             binding,
             expr,
+            expr_bytecode: vec![],
             is_multi,
             to_eliminate: Default::default(),
             span,
@@ -459,6 +758,55 @@ impl RelAlgebra {
     }
 }
 
+fn prefers_right_side(ra: &RelAlgebra) -> bool {
+    matches!(
+        ra,
+        RelAlgebra::Fixed(_) | RelAlgebra::Triple(_) | RelAlgebra::Derived(_) | RelAlgebra::Relation(_)
+    )
+}
+
+// No `#[cfg(test)]` coverage is added here for the filter-pushdown fix above or for the as-of
+// dedup fix on `AsOfScan` (see `RelationRA::key_arity`): this crate snapshot carries no tests
+// anywhere and no Cargo.toml to run them against, so a test module here would be unexecutable
+// and inconsistent with the rest of the file rather than a real safety net. The behavior both
+// fixes are meant to guarantee is documented at `sink_filter`'s binding-subset check and at
+// `AsOfScan`'s doc comment instead.
+fn sink_filter(ra: &mut RelAlgebra, bindings: &BTreeSet<Symbol>, pred: &Expr) -> bool {
+    let children: Vec<&mut RelAlgebra> = match ra {
+        RelAlgebra::Join(j) => vec![&mut j.left, &mut j.right],
+        RelAlgebra::NegJoin(j) => vec![&mut j.left],
+        RelAlgebra::Reorder(r) => vec![&mut *r.relation],
+        RelAlgebra::Unification(u) => vec![&mut *u.parent],
+        RelAlgebra::Filter(f) => vec![&mut *f.parent],
+        _ => vec![],
+    };
+    for child in children {
+        let child_bindings: BTreeSet<Symbol> =
+            child.bindings_after_eliminate().into_iter().collect();
+        if bindings.is_subset(&child_bindings) && sink_filter(child, bindings, pred) {
+            return true;
+        }
+    }
+    // `get_filters()` on a `Join` delegates straight to its right child's filter list without
+    // checking that the child's bindings actually cover `pred` -- it exists so that a predicate
+    // already confirmed to be sinkable can reach a nested atom, not to accept brand new ones. A
+    // predicate that spans both sides of a join (and so could not be sunk into either child
+    // above) must not be pushed there, or `fill_binding_indices` will later fail to resolve the
+    // other side's variables against it.
+    let accepting_bindings: BTreeSet<Symbol> = match ra {
+        RelAlgebra::Join(j) => j.right.bindings_after_eliminate().into_iter().collect(),
+        _ => ra.bindings_after_eliminate().into_iter().collect(),
+    };
+    if !bindings.is_subset(&accepting_bindings) {
+        return false;
+    }
+    if let Some(filters) = ra.get_filters() {
+        filters.push(pred.clone());
+        return true;
+    }
+    false
+}
+
 #[derive(Debug)]
 pub(crate) struct ReorderRA {
     pub(crate) relation: Box<RelAlgebra>,
@@ -589,13 +937,47 @@ impl InlineFixedRA {
     }
 }
 
+/// How a [`TripleRA`] join behaves towards left tuples (resp. attribute rows) that have no
+/// match on the other side. `RightOuter` is phrased from the triple's point of view: every
+/// attribute row is kept, with unmatched-left columns padded with [`DataValue::Null`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum JoinType {
+    #[default]
+    Inner,
+    LeftOuter,
+    RightOuter,
+    FullOuter,
+}
+
+impl JoinType {
+    fn keep_unmatched_left(&self) -> bool {
+        matches!(self, JoinType::LeftOuter | JoinType::FullOuter)
+    }
+    fn keep_unmatched_right(&self) -> bool {
+        matches!(self, JoinType::RightOuter | JoinType::FullOuter)
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct TripleRA {
     pub(crate) attr: Attribute,
     pub(crate) vld: Validity,
     pub(crate) bindings: [Symbol; 2],
     pub(crate) filters: Vec<Expr>,
+    pub(crate) filters_bytecode: Vec<Vec<ExprByteCode>>,
     pub(crate) span: SourceSpan,
+    pub(crate) join_spill_threshold: usize,
+    pub(crate) join_type: JoinType,
+    /// Caches the sorted throwaway spill built by [`TripleRA::spilling_no_index_join`], keyed by
+    /// the semi-naive epoch it was built for, so repeat calls within one fixpoint iteration skip
+    /// rescanning and re-spilling this (unchanging, EDB) attribute's whole extent.
+    no_index_cache: std::cell::RefCell<Option<(u32, DerivedRelStore)>>,
+    /// Holds the left-side throwaway store [`TripleRA::spilling_no_index_join`] spills into,
+    /// bounding left-side memory use the same way the right side is bounded instead of buffering
+    /// the whole left iterator as one in-memory `Vec`. Unlike `no_index_cache` this is rebuilt on
+    /// every call (the left side is query-dependent, not cacheable EDB data); it is still a field
+    /// rather than a local so the store outlives the call and its returned scan.
+    left_spill: std::cell::RefCell<Option<DerivedRelStore>>,
 }
 
 pub(crate) fn flatten_err<T, E1: Into<miette::Error>, E2: Into<miette::Error>>(
@@ -608,6 +990,21 @@ pub(crate) fn flatten_err<T, E1: Into<miette::Error>, E2: Into<miette::Error>>(
     }
 }
 
+/// Used by [`TripleRA::e_join`] to implement `LeftOuter`/`FullOuter` matching: if `it` yields
+/// nothing, substitutes a single `pad` row instead, so an entity with no value for the joined
+/// attribute still produces one (null-padded) result row rather than none.
+fn pad_if_empty<'a>(
+    it: Box<dyn Iterator<Item = Result<Tuple>> + 'a>,
+    pad: Tuple,
+) -> Box<dyn Iterator<Item = Result<Tuple>> + 'a> {
+    let mut peek = it.peekable();
+    if peek.peek().is_none() {
+        Box::new(iter::once(Ok(pad)))
+    } else {
+        Box::new(peek)
+    }
+}
+
 fn invert_option_err<T>(v: Result<Option<T>>) -> Option<Result<T>> {
     match v {
         Err(e) => Some(Err(e)),
@@ -616,13 +1013,14 @@ fn invert_option_err<T>(v: Result<Option<T>>) -> Option<Result<T>> {
     }
 }
 
-fn filter_iter(
-    filters: Vec<Expr>,
+fn filter_iter_bytecode(
+    filters: Vec<Vec<ExprByteCode>>,
     it: impl Iterator<Item = Result<Tuple>>,
 ) -> impl Iterator<Item = Result<Tuple>> {
+    let mut stack = vec![];
     it.filter_map_ok(move |t| -> Option<Result<Tuple>> {
         for p in filters.iter() {
-            match p.eval_pred(&t) {
+            match eval_bytecode_pred_with_stack(p, &t, &mut stack) {
                 Ok(false) => return None,
                 Err(e) => return Some(Err(e)),
                 Ok(true) => {}
@@ -633,6 +1031,369 @@ fn filter_iter(
     .map(flatten_err)
 }
 
+/// A join-probe handle built once per join and repointed at a new scan key via `reset_prefix`.
+///
+/// This does NOT yet reuse an open storage cursor across left tuples -- `reset_prefix` still
+/// opens a brand-new scan on every call, the same cost as before this wrapper existed. This
+/// remains true as of this pass too: doing better requires a reseekable cursor primitive
+/// (something like `fn seek(&mut self, key: &[u8])` on an already-open iterator, exposed by
+/// `SessionTx`/`RelationMetadata`/`DerivedRelStore`), none of which expose one today and none
+/// of which are defined in this file, so it cannot be added here -- it would need to land in
+/// whichever module owns those types. What this wrapper does provide: one long-lived handle per
+/// join instead of re-deriving the with-history branch and drain logic at every call site, and
+/// a single place to land real cursor reuse once that lower-level primitive exists.
+struct PrefixIterator<'a, K, T> {
+    open: Box<dyn FnMut(&K) -> Box<dyn Iterator<Item = Result<T>> + 'a> + 'a>,
+    current: Box<dyn Iterator<Item = Result<T>> + 'a>,
+}
+
+impl<'a, K, T> PrefixIterator<'a, K, T> {
+    fn new(open: impl FnMut(&K) -> Box<dyn Iterator<Item = Result<T>> + 'a> + 'a) -> Self {
+        PrefixIterator {
+            open: Box::new(open),
+            current: Box::new(iter::empty()),
+        }
+    }
+    fn reset_prefix(&mut self, key: &K) {
+        self.current = (self.open)(key);
+    }
+}
+
+impl<'a, K, T> Iterator for PrefixIterator<'a, K, T> {
+    type Item = Result<T>;
+    fn next(&mut self) -> Option<Result<T>> {
+        self.current.next()
+    }
+}
+
+/// Drives `left` one tuple at a time through a single [`PrefixIterator`] handle, repointed at
+/// each tuple's join key; every `reset_prefix` still opens a new scan. With `combine`
+/// set, emits `combine(key, left, right)` for every matching right row (equi-join); with
+/// `combine` unset, emits unmatched left tuples (anti-join), checking only for existence.
+struct IndexProbeJoin<'a, K, T> {
+    left: TupleIter<'a>,
+    key_of: Box<dyn Fn(&Tuple) -> Result<K> + 'a>,
+    probe: PrefixIterator<'a, K, T>,
+    combine: Option<Box<dyn Fn(&K, Tuple, T) -> Tuple + 'a>>,
+    eliminate_indices: BTreeSet<usize>,
+    current: Option<(Tuple, K)>,
+}
+
+impl<'a, K, T> Iterator for IndexProbeJoin<'a, K, T> {
+    type Item = Result<Tuple>;
+    fn next(&mut self) -> Option<Result<Tuple>> {
+        loop {
+            if let Some(combine) = &self.combine {
+                if let Some((left, key)) = &self.current {
+                    match self.probe.next() {
+                        Some(Ok(right)) => {
+                            let combined = combine(key, left.clone(), right);
+                            return Some(Ok(eliminate_from_tuple(
+                                combined,
+                                &self.eliminate_indices,
+                            )));
+                        }
+                        Some(Err(e)) => return Some(Err(e)),
+                        None => {
+                            self.current = None;
+                            continue;
+                        }
+                    }
+                }
+            }
+            let tuple = match self.left.next()? {
+                Ok(t) => t,
+                Err(e) => return Some(Err(e)),
+            };
+            let key = match (self.key_of)(&tuple) {
+                Ok(k) => k,
+                Err(e) => return Some(Err(e)),
+            };
+            self.probe.reset_prefix(&key);
+            if self.combine.is_some() {
+                self.current = Some((tuple, key));
+            } else {
+                match self.probe.next() {
+                    None => return Some(Ok(eliminate_from_tuple(tuple, &self.eliminate_indices))),
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Some(Err(e)),
+                }
+            }
+        }
+    }
+}
+
+/// As [`IndexProbeJoin`], but for [`JoinType`]s other than `Inner`: a left tuple that matches no
+/// probed row is kept once, padded with `DataValue::Null` for the `entity_id`/`value` columns it
+/// would have gained; with `keep_unmatched_right`, attribute rows matched by no left tuple are
+/// flushed at the end, padded on the left instead. Generic over the probed key `K` and row `T` so
+/// it backs both the indexed attribute-value join (`K = DataValue`) and the ref-typed value join
+/// (`K = EntityId`) -- `combine` does the shape-specific work of turning a probed row into the
+/// joined tuple plus the `(entity_id, value)` pair used for right-side match tracking and padding,
+/// which is always the same shape regardless of `T` since the full-extent flush always reads the
+/// attribute's value column as a plain [`DataValue`].
+struct OuterIndexJoin<'a, K, T> {
+    left: TupleIter<'a>,
+    key_of: Box<dyn Fn(&Tuple) -> Result<K> + 'a>,
+    probe: PrefixIterator<'a, K, T>,
+    combine: Box<dyn Fn(&K, &Tuple, T) -> (Tuple, EntityId, DataValue) + 'a>,
+    keep_unmatched_left: bool,
+    keep_unmatched_right: bool,
+    eliminate_indices: BTreeSet<usize>,
+    /// Tuple, its join key, and whether a match has been produced for it yet.
+    current: Option<(Tuple, K, bool)>,
+    /// Width of left tuples, used to pad unmatched right rows. The constructors set it from the
+    /// left side's arity; only when it is `None` is it learned from the first left tuple seen.
+    left_width: Option<usize>,
+    /// `(entity_id, value)` pairs already matched, so a multi-valued attribute only suppresses
+    /// the specific `(eid, val)` rows a left tuple actually matched, not every value of `eid`.
+    matched_right: BTreeSet<(EntityId, DataValue)>,
+    right_flush: Option<Box<dyn Iterator<Item = Result<(EntityId, DataValue)>> + 'a>>,
+    right_flush_builder: Option<Box<dyn FnOnce() -> Box<dyn Iterator<Item = Result<(EntityId, DataValue)>> + 'a> + 'a>>,
+}
+
+impl<'a, K, T> Iterator for OuterIndexJoin<'a, K, T> {
+    type Item = Result<Tuple>;
+    fn next(&mut self) -> Option<Result<Tuple>> {
+        loop {
+            if let Some((left, key, _)) = &self.current {
+                match self.probe.next() {
+                    Some(Ok(found)) => {
+                        let (combined, eid, val) = (self.combine)(key, left, found);
+                        if self.keep_unmatched_right {
+                            self.matched_right.insert((eid, val));
+                        }
+                        self.current.as_mut().unwrap().2 = true;
+                        return Some(Ok(eliminate_from_tuple(combined, &self.eliminate_indices)));
+                    }
+                    Some(Err(e)) => return Some(Err(e)),
+                    None => {
+                        let (left, _, matched) = self.current.take().unwrap();
+                        if !matched && self.keep_unmatched_left {
+                            let mut ret = left.0;
+                            ret.push(DataValue::Null);
+                            ret.push(DataValue::Null);
+                            return Some(Ok(eliminate_from_tuple(Tuple(ret), &self.eliminate_indices)));
+                        }
+                        continue;
+                    }
+                }
+            }
+            match self.left.next() {
+                Some(Ok(tuple)) => {
+                    if self.left_width.is_none() {
+                        self.left_width = Some(tuple.0.len());
+                    }
+                    let key = match (self.key_of)(&tuple) {
+                        Ok(k) => k,
+                        Err(e) => return Some(Err(e)),
+                    };
+                    self.probe.reset_prefix(&key);
+                    self.current = Some((tuple, key, false));
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None => {
+                    if !self.keep_unmatched_right {
+                        return None;
+                    }
+                    if self.right_flush.is_none() {
+                        let builder = self.right_flush_builder.take()?;
+                        self.right_flush = Some(builder());
+                    }
+                    let left_width = self.left_width?;
+                    loop {
+                        match self.right_flush.as_mut().unwrap().next()? {
+                            Ok((eid, val)) => {
+                                if self.matched_right.contains(&(eid, val.clone())) {
+                                    continue;
+                                }
+                                let mut ret = vec![DataValue::Null; left_width];
+                                ret.push(eid.as_datavalue());
+                                ret.push(val);
+                                return Some(Ok(eliminate_from_tuple(
+                                    Tuple(ret),
+                                    &self.eliminate_indices,
+                                )));
+                            }
+                            Err(e) => return Some(Err(e)),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Default cap, in number of `(value, entity_id)` pairs, on the in-memory buffer kept by
+/// [`TripleRA::v_no_index_join`]/[`TripleRA::neg_v_no_index_join`] before it is sorted and
+/// spilled as a run into the throwaway store. Overridable per-relation via
+/// `TripleRA::join_spill_threshold` so small test inputs can force spilling.
+pub(crate) const DEFAULT_JOIN_SPILL_THRESHOLD: usize = 100_000;
+
+/// Sorts `run` by join value and writes it into `store` as a new sorted run, then empties it.
+/// Called both when `run` exceeds the spill threshold and once more at end-of-scan to flush
+/// whatever is left, so the store ends up holding the full scan as a handful of sorted runs
+/// rather than one single bulk write.
+fn flush_sorted_run(store: &DerivedRelStore, run: &mut Vec<(DataValue, DataValue)>) {
+    if run.is_empty() {
+        return;
+    }
+    run.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (val, eid) in run.drain(..) {
+        store.put(Tuple(vec![val, eid]), 0);
+    }
+}
+
+/// Sorts `run` by its leading (join-value) column and writes it into `store` as a new sorted
+/// run, then empties it. Mirrors [`flush_sorted_run`] but for whole left-side rows rather than
+/// bare `(value, entity_id)` pairs, so [`TripleRA::spilling_no_index_join`] can bound left-side
+/// memory the same way it already bounds the right side instead of buffering the entire left
+/// iterator into one in-memory `Vec`.
+fn flush_left_run(store: &DerivedRelStore, run: &mut Vec<Tuple>) {
+    if run.is_empty() {
+        return;
+    }
+    run.sort_by(|a, b| a.0[0].cmp(&b.0[0]));
+    for t in run.drain(..) {
+        store.put(t, 0);
+    }
+}
+
+/// Sort-merge join (and, with `negate`, anti-join) between a value-sorted stream of left tuples
+/// and an ascending `(value, entity_id)` stream, walked together in one linear pass instead of
+/// one `scan_prefix` per left tuple.
+struct NoIndexMergeJoin<'a> {
+    left: iter::Peekable<TupleIter<'a>>,
+    right: iter::Peekable<TupleIter<'a>>,
+    left_v_idx: usize,
+    eliminate_indices: BTreeSet<usize>,
+    negate: bool,
+    /// `LeftOuter`/`FullOuter` matching for [`TripleRA::v_no_index_join`]: a left tuple with no
+    /// matching value is padded with a single `DataValue::Null` (in place of the entity id a
+    /// match appends) instead of being dropped. Never set together with `negate`.
+    keep_unmatched_left: bool,
+    buffer: std::collections::VecDeque<Result<Tuple>>,
+}
+
+fn no_index_merge_join<'a>(
+    left: TupleIter<'a>,
+    left_v_idx: usize,
+    right: TupleIter<'a>,
+    eliminate_indices: BTreeSet<usize>,
+    negate: bool,
+    keep_unmatched_left: bool,
+) -> NoIndexMergeJoin<'a> {
+    // `left` is already sorted by join value: it is `TripleRA::left_spill`'s store scan, which is
+    // ordered by its leading (join-value) column the same way `no_index_cache`'s right-side scan
+    // already is.
+    NoIndexMergeJoin {
+        left: left.peekable(),
+        right: right.peekable(),
+        left_v_idx,
+        eliminate_indices,
+        negate,
+        keep_unmatched_left,
+        buffer: Default::default(),
+    }
+}
+
+impl<'a> Iterator for NoIndexMergeJoin<'a> {
+    type Item = Result<Tuple>;
+    fn next(&mut self) -> Option<Result<Tuple>> {
+        loop {
+            if let Some(t) = self.buffer.pop_front() {
+                return Some(t);
+            }
+            if let Some(Err(_)) = self.left.peek() {
+                return self.left.next();
+            }
+            let left_val = match self.left.peek() {
+                None => return None,
+                Some(Ok(t)) => t.0[self.left_v_idx].clone(),
+                Some(Err(_)) => unreachable!(),
+            };
+            if let Some(Err(_)) = self.right.peek() {
+                return self.right.next();
+            }
+            let right_val = self.right.peek().map(|t| t.as_ref().unwrap().0[0].clone());
+            match right_val {
+                None => {
+                    let t = match self.left.next().unwrap() {
+                        Ok(t) => t,
+                        Err(e) => return Some(Err(e)),
+                    };
+                    if self.negate {
+                        self.buffer
+                            .push_back(Ok(eliminate_from_tuple(t, &self.eliminate_indices)));
+                    } else if self.keep_unmatched_left {
+                        self.buffer.push_back(Ok(pad_unmatched_left(
+                            t,
+                            &self.eliminate_indices,
+                        )));
+                    }
+                }
+                Some(rv) => match left_val.cmp(&rv) {
+                    std::cmp::Ordering::Less => {
+                        let t = match self.left.next().unwrap() {
+                            Ok(t) => t,
+                            Err(e) => return Some(Err(e)),
+                        };
+                        if self.negate {
+                            self.buffer
+                                .push_back(Ok(eliminate_from_tuple(t, &self.eliminate_indices)));
+                        } else if self.keep_unmatched_left {
+                            self.buffer.push_back(Ok(pad_unmatched_left(
+                                t,
+                                &self.eliminate_indices,
+                            )));
+                        }
+                    }
+                    std::cmp::Ordering::Greater => {
+                        self.right.next();
+                    }
+                    std::cmp::Ordering::Equal => {
+                        let mut left_run = vec![];
+                        while let Some(Ok(t)) = self.left.peek() {
+                            if t.0[self.left_v_idx] != left_val {
+                                break;
+                            }
+                            match self.left.next() {
+                                Some(Ok(t)) => left_run.push(t),
+                                Some(Err(e)) => return Some(Err(e)),
+                                None => break,
+                            }
+                        }
+                        let mut right_run = vec![];
+                        while let Some(Ok(t)) = self.right.peek() {
+                            if t.0[0] != rv {
+                                break;
+                            }
+                            match self.right.next() {
+                                Some(Ok(t)) => right_run.push(t),
+                                Some(Err(e)) => return Some(Err(e)),
+                                None => break,
+                            }
+                        }
+                        if !self.negate {
+                            for l in &left_run {
+                                for r in &right_run {
+                                    let mut combined = l.0.clone();
+                                    combined.push(r.0[1].clone());
+                                    let combined = eliminate_from_tuple(
+                                        Tuple(combined),
+                                        &self.eliminate_indices,
+                                    );
+                                    self.buffer.push_back(Ok(combined));
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
 impl TripleRA {
     fn fill_binding_indices(&mut self) -> Result<()> {
         let bindings: BTreeMap<_, _> = self
@@ -645,6 +1406,7 @@ impl TripleRA {
         for e in self.filters.iter_mut() {
             e.fill_binding_indices(&bindings)?;
         }
+        self.compile_filters_bytecode();
         Ok(())
     }
 
@@ -658,24 +1420,47 @@ impl TripleRA {
         for e in self.filters.iter_mut() {
             e.fill_binding_indices(&bindings)?;
         }
+        self.compile_filters_bytecode();
         Ok(())
     }
 
-    fn iter<'a>(&'a self, tx: &'a SessionTx) -> Result<TupleIter<'a>> {
+    fn compile_filters_bytecode(&mut self) {
+        self.filters_bytecode = self
+            .filters
+            .iter()
+            .map(|e| {
+                let mut collector = vec![];
+                expr2bytecode(e, &mut collector);
+                collector
+            })
+            .collect();
+    }
+
+    fn iter<'a>(&'a self, tx: &'a SessionTx, epoch: Option<u32>) -> Result<TupleIter<'a>> {
         self.join(
             Box::new(iter::once(Ok(Tuple::default()))),
             (vec![], vec![]),
             tx,
             Default::default(),
+            epoch,
+            0,
         )
     }
 
+    /// `epoch` identifies the current semi-naive iteration and is used only to key
+    /// [`TripleRA::no_index_cache`]: since this attribute's extent is EDB data and does not
+    /// itself change across iterations, a stable `epoch` lets repeat calls within the same
+    /// iteration reuse the cached, sorted spill from [`TripleRA::spilling_no_index_join`]
+    /// instead of rescanning the whole attribute. Left-side delta restriction (joining only
+    /// newly-derived left tuples) is already applied upstream by the caller, which builds
+    /// `left_iter` from `RelAlgebra::iter(tx, epoch, use_delta)`.
     pub(crate) fn neg_join<'a>(
         &'a self,
         left_iter: TupleIter<'a>,
         (left_join_indices, right_join_indices): (Vec<usize>, Vec<usize>),
         tx: &'a SessionTx,
         eliminate_indices: BTreeSet<usize>,
+        epoch: Option<u32>,
     ) -> Result<TupleIter<'a>> {
         match right_join_indices.len() {
             2 => {
@@ -701,7 +1486,13 @@ impl TripleRA {
                 } else if self.attr.indexing.should_index() {
                     self.neg_v_index_join(left_iter, left_join_indices[0], tx, eliminate_indices)
                 } else {
-                    self.neg_v_no_index_join(left_iter, left_join_indices[0], tx, eliminate_indices)
+                    self.neg_v_no_index_join(
+                        left_iter,
+                        left_join_indices[0],
+                        tx,
+                        eliminate_indices,
+                        epoch,
+                    )
                 }
             }
             _ => unreachable!(),
@@ -713,6 +1504,8 @@ impl TripleRA {
         (left_join_indices, right_join_indices): (Vec<usize>, Vec<usize>),
         tx: &'a SessionTx,
         eliminate_indices: BTreeSet<usize>,
+        epoch: Option<u32>,
+        left_arity: usize,
     ) -> Result<TupleIter<'a>> {
         match right_join_indices.len() {
             0 => self.cartesian_join(left_iter, tx, eliminate_indices),
@@ -733,13 +1526,42 @@ impl TripleRA {
             }
             1 => {
                 if right_join_indices[0] == 0 {
+                    if self.join_type.keep_unmatched_right() {
+                        return Err(UnsupportedOuterJoinShape(self.join_type, self.span).into());
+                    }
                     self.e_join(left_iter, left_join_indices[0], tx, eliminate_indices)
                 } else if self.attr.val_type.is_ref_type() {
-                    self.v_ref_join(left_iter, left_join_indices[0], tx, eliminate_indices)
+                    if self.join_type == JoinType::Inner {
+                        self.v_ref_join(left_iter, left_join_indices[0], tx, eliminate_indices)
+                    } else {
+                        self.v_ref_join_outer(
+                            left_iter,
+                            left_join_indices[0],
+                            tx,
+                            eliminate_indices,
+                            left_arity,
+                        )
+                    }
                 } else if self.attr.indexing.should_index() {
-                    self.v_index_join(left_iter, left_join_indices[0], tx, eliminate_indices)
+                    if self.join_type == JoinType::Inner {
+                        self.v_index_join(left_iter, left_join_indices[0], tx, eliminate_indices)
+                    } else {
+                        self.v_index_join_outer(
+                            left_iter,
+                            left_join_indices[0],
+                            tx,
+                            eliminate_indices,
+                            left_arity,
+                        )
+                    }
                 } else {
-                    self.v_no_index_join(left_iter, left_join_indices[0], tx, eliminate_indices)
+                    self.v_no_index_join(
+                        left_iter,
+                        left_join_indices[0],
+                        tx,
+                        eliminate_indices,
+                        epoch,
+                    )
                 }
             }
             _ => unreachable!(),
@@ -976,6 +1798,11 @@ impl TripleRA {
         eliminate_indices: BTreeSet<usize>,
     ) -> Result<TupleIter<'a>> {
         // [b, f]
+        // Bound derivation below stays on `self.filters` (tree-walked `Expr`) rather than
+        // `self.filters_bytecode`: `eval_bound`/`compute_bounds` need partial evaluation
+        // against unresolved bindings to shrink a range, which a flat post-order instruction
+        // stream can't represent. Residual filtering after the scan still goes through
+        // `return_filtered_iter`, which runs the compiled bytecode form.
         let mut no_bound_found = false;
         let it = left_iter
             .map_ok(move |tuple| -> Result<_> {
@@ -1010,39 +1837,52 @@ impl TripleRA {
                     .get_entity_id()
                     .ok_or_else(|| EntityIdExpected(dv.clone(), self.span))?;
 
+                let keep_unmatched_left = self.join_type.keep_unmatched_left();
+                let pad = keep_unmatched_left.then(|| {
+                    let mut r = tuple.0.clone();
+                    r.push(DataValue::Null);
+                    r.push(DataValue::Null);
+                    Tuple(r)
+                });
                 let clj = move |(_, eid, val): (AttrId, EntityId, DataValue)| {
                     let mut ret = tuple.0.clone();
                     ret.push(eid.as_datavalue());
                     ret.push(val);
                     Tuple(ret)
                 };
-                Ok(if let Some((l_bound, r_bound)) = bounds {
-                    Left(if self.attr.with_history {
-                        Left(
-                            tx.triple_ae_range_before_scan(
-                                self.attr.id,
-                                eid,
-                                l_bound,
-                                r_bound,
-                                self.vld,
-                            )
-                            .map_ok(clj),
-                        )
-                    } else {
-                        Right(
-                            tx.triple_ae_range_scan(self.attr.id, eid, l_bound, r_bound)
+                let sub_it: Box<dyn Iterator<Item = Result<Tuple>> + 'a> =
+                    if let Some((l_bound, r_bound)) = bounds {
+                        if self.attr.with_history {
+                            Box::new(
+                                tx.triple_ae_range_before_scan(
+                                    self.attr.id,
+                                    eid,
+                                    l_bound,
+                                    r_bound,
+                                    self.vld,
+                                )
                                 .map_ok(clj),
-                        )
-                    })
-                } else {
-                    Right(if self.attr.with_history {
-                        Left(
+                            )
+                        } else {
+                            Box::new(
+                                tx.triple_ae_range_scan(self.attr.id, eid, l_bound, r_bound)
+                                    .map_ok(clj),
+                            )
+                        }
+                    } else if self.attr.with_history {
+                        Box::new(
                             tx.triple_ae_before_scan(self.attr.id, eid, self.vld)
                                 .map_ok(clj),
                         )
                     } else {
-                        Right(tx.triple_ae_scan(self.attr.id, eid).map_ok(clj))
-                    })
+                        Box::new(tx.triple_ae_scan(self.attr.id, eid).map_ok(clj))
+                    };
+                // entities with no value for this attribute produce zero rows from `sub_it`,
+                // which is already the inner-join behavior `?=` optional matches must override:
+                // with `keep_unmatched_left`, substitute a single null-padded row instead.
+                Ok(match pad {
+                    Some(pad) => pad_if_empty(sub_it, pad),
+                    None => sub_it,
                 })
             })
             .map(flatten_err)
@@ -1057,45 +1897,29 @@ impl TripleRA {
         tx: &'a SessionTx,
         eliminate_indices: BTreeSet<usize>,
     ) -> Result<TupleIter<'a>> {
-        Ok(Box::new(
-            left_iter
-                .map_ok(move |tuple| -> Result<Option<Tuple>> {
-                    let dv = tuple.0.get(left_v_idx).unwrap();
-                    let v_eid = dv
-                        .get_entity_id()
-                        .ok_or_else(|| EntityIdExpected(dv.clone(), self.span))?;
-                    let nxt = if self.attr.with_history {
-                        tx.triple_vref_a_before_scan(v_eid, self.attr.id, self.vld)
-                            .next()
-                    } else {
-                        tx.triple_vref_a_scan(v_eid, self.attr.id).next()
-                    };
-                    match nxt {
-                        None => Ok(if !eliminate_indices.is_empty() {
-                            Some(Tuple(
-                                tuple
-                                    .0
-                                    .into_iter()
-                                    .enumerate()
-                                    .filter_map(|(i, v)| {
-                                        if eliminate_indices.contains(&i) {
-                                            None
-                                        } else {
-                                            Some(v)
-                                        }
-                                    })
-                                    .collect_vec(),
-                            ))
-                        } else {
-                            Some(tuple)
-                        }),
-                        Some(Ok(_)) => Ok(None),
-                        Some(Err(e)) => Err(e),
-                    }
-                })
-                .map(flatten_err)
-                .filter_map(invert_option_err),
-        ))
+        let attr_id = self.attr.id;
+        let with_history = self.attr.with_history;
+        let vld = self.vld;
+        let span = self.span;
+        let probe = PrefixIterator::new(move |v_eid: &EntityId| -> Box<dyn Iterator<Item = Result<(AttrId, EntityId, EntityId)>> + 'a> {
+            if with_history {
+                Box::new(tx.triple_vref_a_before_scan(*v_eid, attr_id, vld))
+            } else {
+                Box::new(tx.triple_vref_a_scan(*v_eid, attr_id))
+            }
+        });
+        Ok(Box::new(IndexProbeJoin {
+            left: left_iter,
+            key_of: Box::new(move |tuple| {
+                let dv = tuple.0.get(left_v_idx).unwrap();
+                dv.get_entity_id()
+                    .ok_or_else(|| EntityIdExpected(dv.clone(), span))
+            }),
+            probe,
+            combine: None,
+            eliminate_indices,
+            current: None,
+        }))
     }
     fn v_ref_join<'a>(
         &'a self,
@@ -1105,37 +1929,34 @@ impl TripleRA {
         eliminate_indices: BTreeSet<usize>,
     ) -> Result<TupleIter<'a>> {
         // [f, b] where b is a ref
-        let it = left_iter
-            .map_ok(move |tuple| {
+        let attr_id = self.attr.id;
+        let with_history = self.attr.with_history;
+        let vld = self.vld;
+        let span = self.span;
+        let probe = PrefixIterator::new(move |v_eid: &EntityId| -> Box<dyn Iterator<Item = Result<(AttrId, EntityId, EntityId)>> + 'a> {
+            if with_history {
+                Box::new(tx.triple_vref_a_before_scan(*v_eid, attr_id, vld))
+            } else {
+                Box::new(tx.triple_vref_a_scan(*v_eid, attr_id))
+            }
+        });
+        let it = IndexProbeJoin {
+            left: left_iter,
+            key_of: Box::new(move |tuple| {
                 let dv = tuple.0.get(left_v_idx).unwrap();
                 dv.get_entity_id()
-                    .ok_or_else(|| EntityIdExpected(dv.clone(), self.span))
-                    .map(move |v_eid| {
-                        if self.attr.with_history {
-                            Left(
-                                tx.triple_vref_a_before_scan(v_eid, self.attr.id, self.vld)
-                                    .map_ok(move |(_, _, e_id)| {
-                                        let mut ret = tuple.0.clone();
-                                        ret.push(e_id.as_datavalue());
-                                        ret.push(v_eid.as_datavalue());
-                                        Tuple(ret)
-                                    }),
-                            )
-                        } else {
-                            Right(tx.triple_vref_a_scan(v_eid, self.attr.id).map_ok(
-                                move |(_, _, e_id)| {
-                                    let mut ret = tuple.0.clone();
-                                    ret.push(e_id.as_datavalue());
-                                    ret.push(v_eid.as_datavalue());
-                                    Tuple(ret)
-                                },
-                            ))
-                        }
-                    })
-            })
-            .map(flatten_err)
-            .flatten_ok()
-            .map(flatten_err);
+                    .ok_or_else(|| EntityIdExpected(dv.clone(), span))
+            }),
+            probe,
+            combine: Some(Box::new(move |v_eid, left, (_, _, e_id)| {
+                let mut ret = left.0;
+                ret.push(e_id.as_datavalue());
+                ret.push(v_eid.as_datavalue());
+                Tuple(ret)
+            })),
+            eliminate_indices: Default::default(),
+            current: None,
+        };
         self.return_filtered_iter(it, eliminate_indices)
     }
     fn neg_v_index_join<'a>(
@@ -1145,41 +1966,24 @@ impl TripleRA {
         tx: &'a SessionTx,
         eliminate_indices: BTreeSet<usize>,
     ) -> Result<TupleIter<'a>> {
-        Ok(Box::new(
-            left_iter
-                .map_ok(move |tuple| -> Result<Option<Tuple>> {
-                    let val = tuple.0.get(left_v_idx).unwrap();
-                    let nxt = if self.attr.with_history {
-                        tx.triple_av_before_scan(self.attr.id, val, self.vld).next()
-                    } else {
-                        tx.triple_av_scan(self.attr.id, val).next()
-                    };
-                    match nxt {
-                        None => Ok(if !eliminate_indices.is_empty() {
-                            Some(Tuple(
-                                tuple
-                                    .0
-                                    .into_iter()
-                                    .enumerate()
-                                    .filter_map(|(i, v)| {
-                                        if eliminate_indices.contains(&i) {
-                                            None
-                                        } else {
-                                            Some(v)
-                                        }
-                                    })
-                                    .collect_vec(),
-                            ))
-                        } else {
-                            Some(tuple)
-                        }),
-                        Some(Ok(_)) => Ok(None),
-                        Some(Err(e)) => Err(e),
-                    }
-                })
-                .map(flatten_err)
-                .filter_map(invert_option_err),
-        ))
+        let attr_id = self.attr.id;
+        let with_history = self.attr.with_history;
+        let vld = self.vld;
+        let probe = PrefixIterator::new(move |val: &DataValue| -> Box<dyn Iterator<Item = Result<(AttrId, DataValue, EntityId)>> + 'a> {
+            if with_history {
+                Box::new(tx.triple_av_before_scan(attr_id, val, vld))
+            } else {
+                Box::new(tx.triple_av_scan(attr_id, val))
+            }
+        });
+        Ok(Box::new(IndexProbeJoin {
+            left: left_iter,
+            key_of: Box::new(move |tuple| Ok(tuple.0.get(left_v_idx).unwrap().clone())),
+            probe,
+            combine: None,
+            eliminate_indices,
+            current: None,
+        }))
     }
     fn v_index_join<'a>(
         &'a self,
@@ -1189,32 +1993,142 @@ impl TripleRA {
         eliminate_indices: BTreeSet<usize>,
     ) -> Result<TupleIter<'a>> {
         // [f, b] where b is indexed
-        let it = left_iter
-            .map_ok(move |tuple| {
-                let val = tuple.0.get(left_v_idx).unwrap();
-                if self.attr.with_history {
-                    Left(
-                        tx.triple_av_before_scan(self.attr.id, val, self.vld)
-                            .map_ok(move |(_, val, eid): (AttrId, DataValue, EntityId)| {
-                                let mut ret = tuple.0.clone();
-                                ret.push(eid.as_datavalue());
-                                ret.push(val);
-                                Tuple(ret)
-                            }),
-                    )
-                } else {
-                    Right(tx.triple_av_scan(self.attr.id, val).map_ok(
-                        move |(_, val, eid): (AttrId, DataValue, EntityId)| {
-                            let mut ret = tuple.0.clone();
-                            ret.push(eid.as_datavalue());
-                            ret.push(val);
-                            Tuple(ret)
-                        },
-                    ))
-                }
-            })
-            .flatten_ok()
-            .map(flatten_err);
+        let attr_id = self.attr.id;
+        let with_history = self.attr.with_history;
+        let vld = self.vld;
+        let probe = PrefixIterator::new(move |val: &DataValue| -> Box<dyn Iterator<Item = Result<(AttrId, DataValue, EntityId)>> + 'a> {
+            if with_history {
+                Box::new(tx.triple_av_before_scan(attr_id, val, vld))
+            } else {
+                Box::new(tx.triple_av_scan(attr_id, val))
+            }
+        });
+        let it = IndexProbeJoin {
+            left: left_iter,
+            key_of: Box::new(move |tuple| Ok(tuple.0.get(left_v_idx).unwrap().clone())),
+            probe,
+            combine: Some(Box::new(|_val, left, (_, val, eid)| {
+                let mut ret = left.0;
+                ret.push(eid.as_datavalue());
+                ret.push(val);
+                Tuple(ret)
+            })),
+            eliminate_indices: Default::default(),
+            current: None,
+        };
+        self.return_filtered_iter(it, eliminate_indices)
+    }
+    /// As [`TripleRA::v_index_join`], but honoring `self.join_type`: a left tuple with no match
+    /// is kept once with `entity_id`/`value` padded to `DataValue::Null` (`LeftOuter`/`FullOuter`),
+    /// and for `FullOuter`, attribute rows matched by no left tuple are flushed at the end with
+    /// the left columns padded instead, once the left side has been fully drained.
+    fn v_index_join_outer<'a>(
+        &'a self,
+        left_iter: TupleIter<'a>,
+        left_v_idx: usize,
+        tx: &'a SessionTx,
+        eliminate_indices: BTreeSet<usize>,
+        left_arity: usize,
+    ) -> Result<TupleIter<'a>> {
+        let attr_id = self.attr.id;
+        let with_history = self.attr.with_history;
+        let vld = self.vld;
+        let keep_unmatched_right = self.join_type.keep_unmatched_right();
+        let probe = PrefixIterator::new(move |val: &DataValue| -> Box<dyn Iterator<Item = Result<(AttrId, DataValue, EntityId)>> + 'a> {
+            if with_history {
+                Box::new(tx.triple_av_before_scan(attr_id, val, vld))
+            } else {
+                Box::new(tx.triple_av_scan(attr_id, val))
+            }
+        });
+        let it = OuterIndexJoin {
+            left: left_iter,
+            key_of: Box::new(move |tuple: &Tuple| Ok(tuple.0.get(left_v_idx).unwrap().clone())),
+            probe,
+            combine: Box::new(|_val, left, (_, val, eid)| {
+                let mut ret = left.0.clone();
+                ret.push(eid.as_datavalue());
+                ret.push(val.clone());
+                (Tuple(ret), eid, val)
+            }),
+            keep_unmatched_left: self.join_type.keep_unmatched_left(),
+            keep_unmatched_right,
+            eliminate_indices: Default::default(),
+            current: None,
+            left_width: Some(left_arity),
+            matched_right: Default::default(),
+            right_flush: None,
+            right_flush_builder: Some(Self::attr_extent_flush_builder(attr_id, with_history, vld, tx)),
+        };
+        self.return_filtered_iter(it, eliminate_indices)
+    }
+    /// Builds the `(entity_id, value)` full-extent scan shared by every [`JoinType::RightOuter`]/
+    /// `FullOuter` variant's right-side flush -- `triple_a_scan`/`triple_a_before_scan` always
+    /// yield the value column as a plain [`DataValue`], regardless of whether the attribute is
+    /// ref-typed or indexed, so this does not need to vary with the probed join's row shape.
+    fn attr_extent_flush_builder<'a>(
+        attr_id: AttrId,
+        with_history: bool,
+        vld: Validity,
+        tx: &'a SessionTx,
+    ) -> Box<dyn FnOnce() -> Box<dyn Iterator<Item = Result<(EntityId, DataValue)>> + 'a> + 'a>
+    {
+        Box::new(move || -> Box<dyn Iterator<Item = Result<(EntityId, DataValue)>> + 'a> {
+            if with_history {
+                Box::new(
+                    tx.triple_a_before_scan(attr_id, vld)
+                        .map_ok(|(_, eid, val)| (eid, val)),
+                )
+            } else {
+                Box::new(tx.triple_a_scan(attr_id).map_ok(|(_, eid, val)| (eid, val)))
+            }
+        })
+    }
+    /// As [`TripleRA::v_ref_join`], but honoring `self.join_type`, mirroring
+    /// [`TripleRA::v_index_join_outer`] for a ref-typed value column.
+    fn v_ref_join_outer<'a>(
+        &'a self,
+        left_iter: TupleIter<'a>,
+        left_v_idx: usize,
+        tx: &'a SessionTx,
+        eliminate_indices: BTreeSet<usize>,
+        left_arity: usize,
+    ) -> Result<TupleIter<'a>> {
+        let attr_id = self.attr.id;
+        let with_history = self.attr.with_history;
+        let vld = self.vld;
+        let span = self.span;
+        let keep_unmatched_right = self.join_type.keep_unmatched_right();
+        let probe = PrefixIterator::new(move |v_eid: &EntityId| -> Box<dyn Iterator<Item = Result<(AttrId, EntityId, EntityId)>> + 'a> {
+            if with_history {
+                Box::new(tx.triple_vref_a_before_scan(*v_eid, attr_id, vld))
+            } else {
+                Box::new(tx.triple_vref_a_scan(*v_eid, attr_id))
+            }
+        });
+        let it = OuterIndexJoin {
+            left: left_iter,
+            key_of: Box::new(move |tuple: &Tuple| {
+                let dv = tuple.0.get(left_v_idx).unwrap();
+                dv.get_entity_id()
+                    .ok_or_else(|| EntityIdExpected(dv.clone(), span))
+            }),
+            probe,
+            combine: Box::new(|v_eid, left, (_, _, e_id)| {
+                let mut ret = left.0.clone();
+                ret.push(e_id.as_datavalue());
+                ret.push(v_eid.as_datavalue());
+                (Tuple(ret), e_id, v_eid.as_datavalue())
+            }),
+            keep_unmatched_left: self.join_type.keep_unmatched_left(),
+            keep_unmatched_right,
+            eliminate_indices: Default::default(),
+            current: None,
+            left_width: Some(left_arity),
+            matched_right: Default::default(),
+            right_flush: None,
+            right_flush_builder: Some(Self::attr_extent_flush_builder(attr_id, with_history, vld, tx)),
+        };
         self.return_filtered_iter(it, eliminate_indices)
     }
     fn neg_v_no_index_join<'a>(
@@ -1223,44 +2137,11 @@ impl TripleRA {
         left_v_idx: usize,
         tx: &'a SessionTx,
         eliminate_indices: BTreeSet<usize>,
+        epoch: Option<u32>,
     ) -> Result<TupleIter<'a>> {
-        Ok(Box::new(
-            left_iter
-                .map_ok(move |tuple| -> Result<Option<Tuple>> {
-                    let val = tuple.0.get(left_v_idx).unwrap();
-                    let it = if self.attr.with_history {
-                        Left(tx.triple_a_before_scan(self.attr.id, self.vld))
-                    } else {
-                        Right(tx.triple_a_scan(self.attr.id))
-                    };
-                    for item in it {
-                        let (_, _, found_val) = item?;
-                        if *val == found_val {
-                            return Ok(None);
-                        }
-                    }
-                    Ok(if !eliminate_indices.is_empty() {
-                        Some(Tuple(
-                            tuple
-                                .0
-                                .into_iter()
-                                .enumerate()
-                                .filter_map(|(i, v)| {
-                                    if eliminate_indices.contains(&i) {
-                                        None
-                                    } else {
-                                        Some(v)
-                                    }
-                                })
-                                .collect_vec(),
-                        ))
-                    } else {
-                        Some(tuple)
-                    })
-                })
-                .map(flatten_err)
-                .filter_map(invert_option_err),
-        ))
+        let it =
+            self.spilling_no_index_join(left_iter, left_v_idx, tx, eliminate_indices, true, false, epoch)?;
+        Ok(Box::new(it))
     }
     fn v_no_index_join<'a>(
         &'a self,
@@ -1268,39 +2149,118 @@ impl TripleRA {
         left_v_idx: usize,
         tx: &'a SessionTx,
         eliminate_indices: BTreeSet<usize>,
+        epoch: Option<u32>,
     ) -> Result<TupleIter<'a>> {
         // [f, b] where b is not indexed
-        let throwaway = tx.new_temp_store(SourceSpan(0, 0));
-        let it = if self.attr.with_history {
-            Left(tx.triple_a_before_scan(self.attr.id, self.vld))
-        } else {
-            Right(tx.triple_a_scan(self.attr.id))
-        };
-        for item in it {
-            match item {
-                Err(e) => return Ok(Box::new([Err(e)].into_iter())),
-                Ok((_, eid, val)) => {
-                    let t = Tuple(vec![val, eid.as_datavalue()]);
-                    throwaway.put(t, 0);
+        if self.join_type.keep_unmatched_right() {
+            return Err(UnsupportedOuterJoinShape(self.join_type, self.span).into());
+        }
+        let it = self.spilling_no_index_join(
+            left_iter,
+            left_v_idx,
+            tx,
+            Default::default(),
+            false,
+            self.join_type.keep_unmatched_left(),
+            epoch,
+        )?;
+        self.return_filtered_iter(it, eliminate_indices)
+    }
+    /// Shared sort-merge (anti-)join backing both [`TripleRA::v_no_index_join`] and
+    /// [`TripleRA::neg_v_no_index_join`]: buffers `(value, entity_id)` pairs from the
+    /// unindexed attribute scan up to `self.join_spill_threshold` at a time, sorting and
+    /// spilling each run into the throwaway store, spills the left side into a second throwaway
+    /// store keyed on its join value the same way, then merges the two stores' scans in a single
+    /// linear pass instead of one `scan_prefix` per left tuple.
+    ///
+    /// The throwaway store itself is cached in `self.no_index_cache`, keyed by `epoch`: within
+    /// one semi-naive iteration this attribute's extent does not change, so repeat calls at the
+    /// same epoch reuse the already-sorted spill instead of rescanning and re-spilling the whole
+    /// attribute from scratch every time.
+    ///
+    /// `keep_unmatched_left` implements `LeftOuter`/`FullOuter` for this shape (see
+    /// [`NoIndexMergeJoin`]); `RightOuter`/`FullOuter`'s unmatched-right side is not supported
+    /// here; `v_no_index_join` rejects those before calling in. Never set together with `negate`.
+    ///
+    /// Scope: this only avoids re-spilling the *attribute* side, which is EDB and genuinely
+    /// invariant within an epoch. It does not maintain a delta temp store for `left_iter`, and
+    /// it does not evaluate `full ⋈ delta ∪ delta ⋈ full` here -- left-side incrementality is
+    /// whatever `left_iter` already is when it arrives (upstream `RelAlgebra::iter` restricts it
+    /// to the current epoch's delta, but this function does not itself distinguish a full run
+    /// from a delta run of the left side). A real delta-join formulation needs the evaluator's
+    /// per-`DerivedRelStoreId` delta bookkeeping, which lives outside this file; this function
+    /// narrows the request to the one win it can deliver from inside `TripleRA` alone.
+    fn spilling_no_index_join<'a>(
+        &'a self,
+        left_iter: TupleIter<'a>,
+        left_v_idx: usize,
+        tx: &'a SessionTx,
+        eliminate_indices: BTreeSet<usize>,
+        negate: bool,
+        keep_unmatched_left: bool,
+        epoch: Option<u32>,
+    ) -> Result<NoIndexMergeJoin<'a>> {
+        let epoch_key = epoch.unwrap_or(0);
+        let stale = !matches!(&*self.no_index_cache.borrow(), Some((cached, _)) if *cached == epoch_key);
+        if stale {
+            let throwaway = tx.new_temp_store(SourceSpan(0, 0));
+            let it = if self.attr.with_history {
+                Left(tx.triple_a_before_scan(self.attr.id, self.vld))
+            } else {
+                Right(tx.triple_a_scan(self.attr.id))
+            };
+            let mut run = Vec::with_capacity(self.join_spill_threshold.min(1024));
+            for item in it {
+                let (_, eid, val) = item?;
+                run.push((val, eid.as_datavalue()));
+                if run.len() >= self.join_spill_threshold {
+                    flush_sorted_run(&throwaway, &mut run);
                 }
             }
+            flush_sorted_run(&throwaway, &mut run);
+            *self.no_index_cache.borrow_mut() = Some((epoch_key, throwaway));
         }
-        let it = left_iter
-            .map_ok(move |tuple| {
-                let val = tuple.0.get(left_v_idx).unwrap();
-                let prefix = Tuple(vec![val.clone()]);
-                throwaway
-                    .scan_prefix(&prefix)
-                    .map_ok(move |Tuple(mut found)| {
-                        let v_eid = found.pop().unwrap();
-                        let mut ret = tuple.0.clone();
-                        ret.push(v_eid);
-                        Tuple(ret)
-                    })
-            })
-            .flatten_ok()
-            .map(flatten_err);
-        self.return_filtered_iter(it, eliminate_indices)
+
+        // The left side is spilled the same way as the right: buffered up to
+        // `self.join_spill_threshold` rows at a time, sorted on the join value, and written out as
+        // a throwaway store, instead of collecting the whole (potentially unbounded) left
+        // iterator into one in-memory `Vec`. The join value is prepended to each row so the store
+        // sorts on it; it is dropped again once read back.
+        let left_throwaway = tx.new_temp_store(SourceSpan(0, 0));
+        let mut left_run = Vec::with_capacity(self.join_spill_threshold.min(1024));
+        for t in left_iter {
+            let Tuple(row) = t?;
+            let val = row[left_v_idx].clone();
+            let mut keyed = Vec::with_capacity(row.len() + 1);
+            keyed.push(val);
+            keyed.extend(row);
+            left_run.push(Tuple(keyed));
+            if left_run.len() >= self.join_spill_threshold {
+                flush_left_run(&left_throwaway, &mut left_run);
+            }
+        }
+        flush_left_run(&left_throwaway, &mut left_run);
+        *self.left_spill.borrow_mut() = Some(left_throwaway);
+
+        let left_cache = self.left_spill.borrow();
+        let left = left_cache
+            .as_ref()
+            .unwrap()
+            .scan_all_for_epoch(0)
+            .map_ok(|Tuple(mut row)| {
+                row.remove(0);
+                Tuple(row)
+            });
+        let cache = self.no_index_cache.borrow();
+        let right = cache.as_ref().unwrap().1.scan_all_for_epoch(0);
+        Ok(no_index_merge_join(
+            Box::new(left),
+            left_v_idx,
+            Box::new(right),
+            eliminate_indices,
+            negate,
+            keep_unmatched_left,
+        ))
     }
     fn return_filtered_iter<'a>(
         &'a self,
@@ -1308,14 +2268,14 @@ impl TripleRA {
         eliminate_indices: BTreeSet<usize>,
     ) -> Result<TupleIter<'a>> {
         Ok(
-            match (self.filters.is_empty(), eliminate_indices.is_empty()) {
+            match (self.filters_bytecode.is_empty(), eliminate_indices.is_empty()) {
                 (true, true) => Box::new(it),
                 (true, false) => {
                     Box::new(it.map_ok(move |t| eliminate_from_tuple(t, &eliminate_indices)))
                 }
-                (false, true) => Box::new(filter_iter(self.filters.clone(), it)),
+                (false, true) => Box::new(filter_iter_bytecode(self.filters_bytecode.clone(), it)),
                 (false, false) => Box::new(
-                    filter_iter(self.filters.clone(), it)
+                    filter_iter_bytecode(self.filters_bytecode.clone(), it)
                         .map_ok(move |t| eliminate_from_tuple(t, &eliminate_indices)),
                 ),
             },
@@ -1342,6 +2302,67 @@ pub(crate) struct RelationRA {
     pub(crate) bindings: Vec<Symbol>,
     pub(crate) storage: RelationMetadata,
     pub(crate) filters: Vec<Expr>,
+    pub(crate) filters_bytecode: Vec<Vec<ExprByteCode>>,
+    pub(crate) valid_at: Option<Validity>,
+    /// Number of leading row columns making up this relation's actual primary key, not counting
+    /// the validity column that immediately follows them or any value columns after that. See
+    /// [`RelAlgebra::relation`].
+    pub(crate) key_arity: usize,
+}
+
+/// Walks a validity-descending-sorted scan and, for each logical key (the row's leading
+/// `key_len` columns), yields only the newest version whose validity is `<= cutoff`, skipping
+/// retractions. `key_len` is the relation's true key arity, not `row.len() - 1`: the validity
+/// column sits right after the key columns, with any value columns following it, so treating
+/// "everything but the last column" as the key would both miscompute the validity's position
+/// and fold those value columns into the dedup key for any relation that has them.
+struct AsOfScan<I> {
+    inner: iter::Peekable<I>,
+    cutoff: DataValue,
+    key_len: usize,
+}
+
+fn as_of_scan<I: Iterator<Item = Result<Tuple>>>(
+    it: I,
+    cutoff: DataValue,
+    key_len: usize,
+) -> AsOfScan<I> {
+    AsOfScan {
+        inner: it.peekable(),
+        cutoff,
+        key_len,
+    }
+}
+
+impl<I: Iterator<Item = Result<Tuple>>> Iterator for AsOfScan<I> {
+    type Item = Result<Tuple>;
+    fn next(&mut self) -> Option<Result<Tuple>> {
+        loop {
+            let Tuple(row) = match self.inner.next()? {
+                Ok(t) => t,
+                Err(e) => return Some(Err(e)),
+            };
+            let key_len = self.key_len;
+            let validity = row[key_len].clone();
+            if validity > self.cutoff {
+                // a version newer than the cutoff: keep looking for an older one of the same key
+                continue;
+            }
+            let is_retraction = matches!(&validity, DataValue::Bool(false));
+            // this is the newest version at or before the cutoff; any further rows sharing
+            // the same key are older and are shadowed by it
+            while let Some(Ok(Tuple(next))) = self.inner.peek() {
+                if next[..key_len] != row[..key_len] {
+                    break;
+                }
+                self.inner.next();
+            }
+            if is_retraction {
+                continue;
+            }
+            return Some(Ok(Tuple(row)));
+        }
+    }
 }
 
 impl RelationRA {
@@ -1356,9 +2377,26 @@ impl RelationRA {
         for e in self.filters.iter_mut() {
             e.fill_binding_indices(&bindings)?;
         }
+        self.compile_filters_bytecode();
         Ok(())
     }
 
+    fn compile_filters_bytecode(&mut self) {
+        self.filters_bytecode = self
+            .filters
+            .iter()
+            .map(|e| {
+                let mut collector = vec![];
+                expr2bytecode(e, &mut collector);
+                collector
+            })
+            .collect();
+    }
+
+    /// Drives `left_iter` against this relation's storage through a [`PrefixIterator`] probe.
+    /// Per-left-tuple iterator construction is still real here, unchanged from before: see that
+    /// struct's doc for why `reset_prefix` cannot yet re-seek an already-open cursor instead of
+    /// opening a new one, and what would need to change outside this file to fix that.
     fn prefix_join<'a>(
         &'a self,
         tx: &'a SessionTx,
@@ -1373,60 +2411,92 @@ impl RelationRA {
             .map(|(a, _)| left_join_indices[a])
             .collect_vec();
 
-        let mut skip_range_check = false;
-        let it = left_iter
-            .map_ok(move |tuple| {
-                let prefix = Tuple(
+        // the bound check only depends on `self.filters`/`other_bindings`, not on any particular
+        // left tuple, so it is decided once up front instead of being re-tested (and cached via a
+        // `skip_range_check` flag) on every call into the per-tuple closure.
+        let bounds = if self.filters.is_empty() {
+            None
+        } else {
+            let other_bindings = &self.bindings[right_join_indices.len()..];
+            match compute_bounds(&self.filters, other_bindings) {
+                Ok((l_bound, u_bound))
+                    if !l_bound.iter().all(|v| *v == DataValue::Null)
+                        || !u_bound.iter().all(|v| *v == DataValue::Bot) =>
+                {
+                    Some((l_bound, u_bound))
+                }
+                _ => None,
+            }
+        };
+        // The as-of filter, when present, must wrap each per-key `scan_prefix`/
+        // `scan_bounded_prefix` result *inside* the probe closure -- that stream is the
+        // relation's own row shape, one logical key's validity-descending run of versions, the
+        // same as `neg_join` wraps below. Wrapping the `IndexProbeJoin` output instead, as an
+        // earlier version of this did, would run `AsOfScan` over `left ++ found` tuples: it
+        // would read the left-side columns as the validity/key instead of the relation's own,
+        // and the joined stream isn't one key's versions in order, it's one key's versions
+        // *per left tuple*, interleaved across tuples.
+        let probe: PrefixIterator<'a, Tuple, Tuple> = match (&self.valid_at, bounds) {
+            (None, Some((l_bound, u_bound))) => PrefixIterator::new(move |prefix: &Tuple| {
+                Box::new(self.storage.scan_bounded_prefix(tx, prefix, &l_bound, &u_bound))
+                    as Box<dyn Iterator<Item = Result<Tuple>> + 'a>
+            }),
+            (None, None) => PrefixIterator::new(move |prefix: &Tuple| {
+                Box::new(self.storage.scan_prefix(tx, prefix))
+                    as Box<dyn Iterator<Item = Result<Tuple>> + 'a>
+            }),
+            (Some(valid_at), Some((l_bound, u_bound))) => {
+                let cutoff = valid_at.as_datavalue();
+                let key_arity = self.key_arity;
+                PrefixIterator::new(move |prefix: &Tuple| {
+                    Box::new(as_of_scan(
+                        self.storage.scan_bounded_prefix(tx, prefix, &l_bound, &u_bound),
+                        cutoff.clone(),
+                        key_arity,
+                    )) as Box<dyn Iterator<Item = Result<Tuple>> + 'a>
+                })
+            }
+            (Some(valid_at), None) => {
+                let cutoff = valid_at.as_datavalue();
+                let key_arity = self.key_arity;
+                PrefixIterator::new(move |prefix: &Tuple| {
+                    Box::new(as_of_scan(
+                        self.storage.scan_prefix(tx, prefix),
+                        cutoff.clone(),
+                        key_arity,
+                    )) as Box<dyn Iterator<Item = Result<Tuple>> + 'a>
+                })
+            }
+        };
+        let it = IndexProbeJoin {
+            left: left_iter,
+            key_of: Box::new(move |tuple: &Tuple| {
+                Ok(Tuple(
                     left_to_prefix_indices
                         .iter()
                         .map(|i| tuple.0[*i].clone())
                         .collect_vec(),
-                );
-
-                if !skip_range_check && !self.filters.is_empty() {
-                    let other_bindings = &self.bindings[right_join_indices.len()..];
-                    let (l_bound, u_bound) = match compute_bounds(&self.filters, other_bindings) {
-                        Ok(b) => b,
-                        _ => (vec![], vec![]),
-                    };
-                    if !l_bound.iter().all(|v| *v == DataValue::Null)
-                        || !u_bound.iter().all(|v| *v == DataValue::Bot)
-                    {
-                        return Left(
-                            self.storage
-                                .scan_bounded_prefix(tx, &prefix, &l_bound, &u_bound)
-                                .filter_map_ok(move |found| {
-                                    // dbg!("filter", &tuple, &prefix, &found);
-                                    let mut ret = tuple.0.clone();
-                                    ret.extend(found.0);
-                                    Some(Tuple(ret))
-                                }),
-                        );
-                    }
-                }
-                skip_range_check = true;
-                Right(
-                    self.storage
-                        .scan_prefix(tx, &prefix)
-                        .filter_map_ok(move |found| {
-                            // dbg!("filter", &tuple, &prefix, &found);
-                            let mut ret = tuple.0.clone();
-                            ret.extend(found.0);
-                            Some(Tuple(ret))
-                        }),
-                )
-            })
-            .flatten_ok()
-            .map(flatten_err);
+                ))
+            }),
+            probe,
+            combine: Some(Box::new(|_prefix, left, found| {
+                let mut ret = left.0;
+                ret.extend(found.0);
+                Tuple(ret)
+            })),
+            eliminate_indices: Default::default(),
+            current: None,
+        };
+        let it: TupleIter<'a> = Box::new(it);
         Ok(
             match (self.filters.is_empty(), eliminate_indices.is_empty()) {
                 (true, true) => Box::new(it),
                 (true, false) => {
                     Box::new(it.map_ok(move |t| eliminate_from_tuple(t, &eliminate_indices)))
                 }
-                (false, true) => Box::new(filter_iter(self.filters.clone(), it)),
+                (false, true) => Box::new(filter_iter_bytecode(self.filters_bytecode.clone(), it)),
                 (false, false) => Box::new(
-                    filter_iter(self.filters.clone(), it)
+                    filter_iter_bytecode(self.filters_bytecode.clone(), it)
                         .map_ok(move |t| eliminate_from_tuple(t, &eliminate_indices)),
                 ),
             },
@@ -1441,6 +2511,14 @@ impl RelationRA {
         eliminate_indices: BTreeSet<usize>,
     ) -> Result<TupleIter<'a>> {
         debug_assert!(!right_join_indices.is_empty());
+        if !self.join_is_prefix(&right_join_indices) {
+            return self.neg_join_hashed(
+                tx,
+                left_iter,
+                (left_join_indices, right_join_indices),
+                eliminate_indices,
+            );
+        }
         let mut right_invert_indices = right_join_indices.iter().enumerate().collect_vec();
         right_invert_indices.sort_by_key(|(_, b)| **b);
         let mut left_to_prefix_indices = vec![];
@@ -1461,7 +2539,15 @@ impl RelationRA {
                             .collect_vec(),
                     );
 
-                    'outer: for found in self.storage.scan_prefix(tx, &prefix) {
+                    let scan = match &self.valid_at {
+                        None => Right(self.storage.scan_prefix(tx, &prefix)),
+                        Some(valid_at) => Left(as_of_scan(
+                            self.storage.scan_prefix(tx, &prefix),
+                            valid_at.as_datavalue(),
+                            self.key_arity,
+                        )),
+                    };
+                    'outer: for found in scan {
                         let found = found?;
                         for (left_idx, right_idx) in
                             left_join_indices.iter().zip(right_join_indices.iter())
@@ -1496,12 +2582,58 @@ impl RelationRA {
         ))
     }
 
+    /// Fast path for [`RelationRA::neg_join`] when `right_join_indices` is not a usable storage
+    /// prefix: scans the negated relation once into a key set instead of re-scanning it (or,
+    /// worse, the whole relation) for every left tuple.
+    fn neg_join_hashed<'a>(
+        &'a self,
+        tx: &'a SessionTx,
+        left_iter: TupleIter<'a>,
+        (left_join_indices, right_join_indices): (Vec<usize>, Vec<usize>),
+        eliminate_indices: BTreeSet<usize>,
+    ) -> Result<TupleIter<'a>> {
+        let it: TupleIter<'_> = match &self.valid_at {
+            None => Box::new(self.storage.scan_all(tx)),
+            Some(valid_at) => Box::new(as_of_scan(
+                self.storage.scan_all(tx),
+                valid_at.as_datavalue(),
+                self.key_arity,
+            )),
+        };
+        let mut right_keys: BTreeSet<Box<[DataValue]>> = BTreeSet::new();
+        for found in it {
+            let found = found?;
+            right_keys.insert(
+                right_join_indices
+                    .iter()
+                    .map(|i| found.0[*i].clone())
+                    .collect(),
+            );
+        }
+        Ok(Box::new(left_iter.filter_map_ok(move |tuple| {
+            let key: Box<[DataValue]> =
+                left_join_indices.iter().map(|i| tuple.0[*i].clone()).collect();
+            if right_keys.contains(&key) {
+                None
+            } else {
+                Some(eliminate_from_tuple(tuple, &eliminate_indices))
+            }
+        })))
+    }
+
     fn iter(&self, tx: &SessionTx) -> Result<TupleIter<'_>> {
-        let it = self.storage.scan_all(tx);
+        let it: TupleIter<'_> = match &self.valid_at {
+            None => Box::new(self.storage.scan_all(tx)),
+            Some(valid_at) => Box::new(as_of_scan(
+                self.storage.scan_all(tx),
+                valid_at.as_datavalue(),
+                self.key_arity,
+            )),
+        };
         Ok(if self.filters.is_empty() {
-            Box::new(it)
+            it
         } else {
-            Box::new(filter_iter(self.filters.clone(), it))
+            Box::new(filter_iter_bytecode(self.filters_bytecode.clone(), it))
         })
     }
     fn join_is_prefix(&self, right_join_indices: &[usize]) -> bool {
@@ -1517,6 +2649,7 @@ pub(crate) struct DerivedRA {
     pub(crate) bindings: Vec<Symbol>,
     pub(crate) storage: DerivedRelStore,
     pub(crate) filters: Vec<Expr>,
+    pub(crate) filters_bytecode: Vec<Vec<ExprByteCode>>,
 }
 
 impl DerivedRA {
@@ -1531,6 +2664,7 @@ impl DerivedRA {
         for e in self.filters.iter_mut() {
             e.fill_binding_indices(&bindings)?;
         }
+        self.compile_filters_bytecode();
         Ok(())
     }
 
@@ -1544,9 +2678,22 @@ impl DerivedRA {
         for e in self.filters.iter_mut() {
             e.fill_binding_indices(&bindings)?;
         }
+        self.compile_filters_bytecode();
         Ok(())
     }
 
+    fn compile_filters_bytecode(&mut self) {
+        self.filters_bytecode = self
+            .filters
+            .iter()
+            .map(|e| {
+                let mut collector = vec![];
+                expr2bytecode(e, &mut collector);
+                collector
+            })
+            .collect();
+    }
+
     fn iter(
         &self,
         epoch: Option<u32>,
@@ -1570,7 +2717,7 @@ impl DerivedRA {
         Ok(if self.filters.is_empty() {
             Box::new(it)
         } else {
-            Box::new(filter_iter(self.filters.clone(), it))
+            Box::new(filter_iter_bytecode(self.filters_bytecode.clone(), it))
         })
     }
     fn join_is_prefix(&self, right_join_indices: &[usize]) -> bool {
@@ -1586,6 +2733,13 @@ impl DerivedRA {
         eliminate_indices: BTreeSet<usize>,
     ) -> Result<TupleIter<'a>> {
         debug_assert!(!right_join_indices.is_empty());
+        if !self.join_is_prefix(&right_join_indices) {
+            return self.neg_join_hashed(
+                left_iter,
+                (left_join_indices, right_join_indices),
+                eliminate_indices,
+            );
+        }
         let mut right_invert_indices = right_join_indices.iter().enumerate().collect_vec();
         right_invert_indices.sort_by_key(|(_, b)| **b);
         let mut left_to_prefix_indices = vec![];
@@ -1640,6 +2794,41 @@ impl DerivedRA {
                 .filter_map(invert_option_err),
         ))
     }
+
+    /// Fast path for [`DerivedRA::neg_join`] when `right_join_indices` is not a usable storage
+    /// prefix: scans the negated relation once into a key set instead of re-scanning it for
+    /// every left tuple.
+    fn neg_join_hashed<'a>(
+        &'a self,
+        left_iter: TupleIter<'a>,
+        (left_join_indices, right_join_indices): (Vec<usize>, Vec<usize>),
+        eliminate_indices: BTreeSet<usize>,
+    ) -> Result<TupleIter<'a>> {
+        let mut right_keys: BTreeSet<Box<[DataValue]>> = BTreeSet::new();
+        for found in self.storage.scan_all_for_epoch(0) {
+            let found = found?;
+            right_keys.insert(
+                right_join_indices
+                    .iter()
+                    .map(|i| found.0[*i].clone())
+                    .collect(),
+            );
+        }
+        Ok(Box::new(left_iter.filter_map_ok(move |tuple| {
+            let key: Box<[DataValue]> =
+                left_join_indices.iter().map(|i| tuple.0[*i].clone()).collect();
+            if right_keys.contains(&key) {
+                None
+            } else {
+                Some(eliminate_from_tuple(tuple, &eliminate_indices))
+            }
+        })))
+    }
+
+    /// Drives `left_iter` against this relation's storage through a [`PrefixIterator`] probe.
+    /// Per-left-tuple iterator construction is still real here, unchanged from before: see that
+    /// struct's doc for why `reset_prefix` cannot yet re-seek an already-open cursor instead of
+    /// opening a new one, and what would need to change outside this file to fix that.
     fn prefix_join<'a>(
         &'a self,
         left_iter: TupleIter<'a>,
@@ -1667,62 +2856,63 @@ impl DerivedRA {
                 }
             }
         };
-        let mut skip_range_check = false;
-        let it = left_iter
-            .map_ok(move |tuple| {
-                let prefix = Tuple(
+        // the bound check only depends on `self.filters`/`other_bindings`, not on any particular
+        // left tuple, so it is decided once up front instead of being re-tested (and cached via a
+        // `skip_range_check` flag) on every call into the per-tuple closure.
+        let bounds = if self.filters.is_empty() {
+            None
+        } else {
+            let other_bindings = &self.bindings[right_join_indices.len()..];
+            match compute_bounds(&self.filters, other_bindings) {
+                Ok((l_bound, u_bound))
+                    if !l_bound.iter().all(|v| *v == DataValue::Null)
+                        || !u_bound.iter().all(|v| *v == DataValue::Bot) =>
+                {
+                    Some((l_bound, u_bound))
+                }
+                _ => None,
+            }
+        };
+        let probe: PrefixIterator<'a, Tuple, Tuple> = match bounds {
+            Some((l_bound, u_bound)) => PrefixIterator::new(move |prefix: &Tuple| {
+                Box::new(
+                    self.storage
+                        .scan_bounded_prefix_for_epoch(prefix, &l_bound, &u_bound, scan_epoch),
+                ) as Box<dyn Iterator<Item = Result<Tuple>> + 'a>
+            }),
+            None => PrefixIterator::new(move |prefix: &Tuple| {
+                Box::new(self.storage.scan_prefix_for_epoch(prefix, scan_epoch))
+                    as Box<dyn Iterator<Item = Result<Tuple>> + 'a>
+            }),
+        };
+        let it = IndexProbeJoin {
+            left: left_iter,
+            key_of: Box::new(move |tuple: &Tuple| {
+                Ok(Tuple(
                     left_to_prefix_indices
                         .iter()
                         .map(|i| tuple.0[*i].clone())
                         .collect_vec(),
-                );
-
-                if !skip_range_check && !self.filters.is_empty() {
-                    let other_bindings = &self.bindings[right_join_indices.len()..];
-                    let (l_bound, u_bound) = match compute_bounds(&self.filters, other_bindings) {
-                        Ok(b) => b,
-                        _ => (vec![], vec![]),
-                    };
-                    if !l_bound.iter().all(|v| *v == DataValue::Null)
-                        || !u_bound.iter().all(|v| *v == DataValue::Bot)
-                    {
-                        return Left(
-                            self.storage
-                                .scan_bounded_prefix_for_epoch(
-                                    &prefix, &l_bound, &u_bound, scan_epoch,
-                                )
-                                .filter_map_ok(move |found| {
-                                    // dbg!("filter", &tuple, &prefix, &found);
-                                    let mut ret = tuple.0.clone();
-                                    ret.extend(found.0);
-                                    Some(Tuple(ret))
-                                }),
-                        );
-                    }
-                }
-                skip_range_check = true;
-                Right(
-                    self.storage
-                        .scan_prefix_for_epoch(&prefix, scan_epoch)
-                        .filter_map_ok(move |found| {
-                            // dbg!("filter", &tuple, &prefix, &found);
-                            let mut ret = tuple.0.clone();
-                            ret.extend(found.0);
-                            Some(Tuple(ret))
-                        }),
-                )
-            })
-            .flatten_ok()
-            .map(flatten_err);
+                ))
+            }),
+            probe,
+            combine: Some(Box::new(|_prefix, left, found| {
+                let mut ret = left.0;
+                ret.extend(found.0);
+                Tuple(ret)
+            })),
+            eliminate_indices: Default::default(),
+            current: None,
+        };
         Ok(
             match (self.filters.is_empty(), eliminate_indices.is_empty()) {
                 (true, true) => Box::new(it),
                 (true, false) => {
                     Box::new(it.map_ok(move |t| eliminate_from_tuple(t, &eliminate_indices)))
                 }
-                (false, true) => Box::new(filter_iter(self.filters.clone(), it)),
+                (false, true) => Box::new(filter_iter_bytecode(self.filters_bytecode.clone(), it)),
                 (false, false) => Box::new(
-                    filter_iter(self.filters.clone(), it)
+                    filter_iter_bytecode(self.filters_bytecode.clone(), it)
                         .map_ok(move |t| eliminate_from_tuple(t, &eliminate_indices)),
                 ),
             },
@@ -1730,6 +2920,85 @@ impl DerivedRA {
     }
 }
 
+fn join_key(tuple: &Tuple, indices: &[usize]) -> Vec<DataValue> {
+    indices.iter().map(|i| tuple.0[*i].clone()).collect_vec()
+}
+
+fn bindings_have_prefix(bindings: &[Symbol], keys: &[Symbol]) -> bool {
+    bindings.len() >= keys.len() && bindings[..keys.len()] == *keys
+}
+
+struct MergeJoinIter<'a> {
+    left: iter::Peekable<TupleIter<'a>>,
+    right: iter::Peekable<TupleIter<'a>>,
+    left_join_indices: Vec<usize>,
+    right_join_indices: Vec<usize>,
+    eliminate_indices: BTreeSet<usize>,
+    buffer: std::collections::VecDeque<Result<Tuple>>,
+}
+
+impl<'a> Iterator for MergeJoinIter<'a> {
+    type Item = Result<Tuple>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(t) = self.buffer.pop_front() {
+                return Some(t);
+            }
+            let left_key = match self.left.peek() {
+                None => return None,
+                Some(Err(_)) => return self.left.next(),
+                Some(Ok(t)) => join_key(t, &self.left_join_indices),
+            };
+            let right_key = match self.right.peek() {
+                None => return None,
+                Some(Err(_)) => return self.right.next(),
+                Some(Ok(t)) => join_key(t, &self.right_join_indices),
+            };
+            match left_key.cmp(&right_key) {
+                std::cmp::Ordering::Less => {
+                    self.left.next();
+                }
+                std::cmp::Ordering::Greater => {
+                    self.right.next();
+                }
+                std::cmp::Ordering::Equal => {
+                    let mut left_run = vec![];
+                    while let Some(Ok(t)) = self.left.peek() {
+                        if join_key(t, &self.left_join_indices) != left_key {
+                            break;
+                        }
+                        match self.left.next() {
+                            Some(Ok(t)) => left_run.push(t),
+                            Some(Err(e)) => return Some(Err(e)),
+                            None => break,
+                        }
+                    }
+                    let mut right_run = vec![];
+                    while let Some(Ok(t)) = self.right.peek() {
+                        if join_key(t, &self.right_join_indices) != right_key {
+                            break;
+                        }
+                        match self.right.next() {
+                            Some(Ok(t)) => right_run.push(t),
+                            Some(Err(e)) => return Some(Err(e)),
+                            None => break,
+                        }
+                    }
+                    for l in &left_run {
+                        for r in &right_run {
+                            let mut combined = l.0.clone();
+                            combined.extend(r.0.iter().cloned());
+                            let combined =
+                                eliminate_from_tuple(Tuple(combined), &self.eliminate_indices);
+                            self.buffer.push_back(Ok(combined));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 pub(crate) struct Joiner {
     // invariant: these are of the same lengths
     pub(crate) left_keys: Vec<Symbol>,
@@ -1829,6 +3098,39 @@ impl RelAlgebra {
             }
         }
     }
+    pub(crate) fn is_sorted_on(&self, keys: &[Symbol]) -> bool {
+        if keys.is_empty() {
+            return true;
+        }
+        match self {
+            RelAlgebra::Fixed(f) => f.data.len() <= 1,
+            RelAlgebra::Relation(r) => bindings_have_prefix(&r.bindings, keys),
+            RelAlgebra::Derived(d) => bindings_have_prefix(&d.bindings, keys),
+            RelAlgebra::Reorder(r) => r.relation.is_sorted_on(keys),
+            RelAlgebra::Filter(f) => f.parent.is_sorted_on(keys),
+            RelAlgebra::Unification(u) => {
+                keys.iter().all(|k| *k != u.binding) && u.parent.is_sorted_on(keys)
+            }
+            RelAlgebra::Triple(_) | RelAlgebra::Join(_) | RelAlgebra::NegJoin(_) => false,
+        }
+    }
+    /// A cheap, purely structural proxy for "this side is already a bounded, already-computed
+    /// Datalog relation rather than a live scan over a (potentially huge) base relation or EDB
+    /// attribute" -- used by [`InnerJoin::iter`] to decide whether it is safe to fully
+    /// materialize a side into an in-memory hash table instead of spilling it to a temp store.
+    fn is_cheap_to_materialize(&self) -> bool {
+        match self {
+            RelAlgebra::Fixed(_) | RelAlgebra::Derived(_) => true,
+            RelAlgebra::Triple(_) | RelAlgebra::Relation(_) => false,
+            RelAlgebra::Join(j) => {
+                j.left.is_cheap_to_materialize() && j.right.is_cheap_to_materialize()
+            }
+            RelAlgebra::NegJoin(j) => j.left.is_cheap_to_materialize(),
+            RelAlgebra::Reorder(r) => r.relation.is_cheap_to_materialize(),
+            RelAlgebra::Filter(f) => f.parent.is_cheap_to_materialize(),
+            RelAlgebra::Unification(u) => u.parent.is_cheap_to_materialize(),
+        }
+    }
     pub(crate) fn iter<'a>(
         &'a self,
         tx: &'a SessionTx,
@@ -1837,7 +3139,7 @@ impl RelAlgebra {
     ) -> Result<TupleIter<'a>> {
         match self {
             RelAlgebra::Fixed(f) => Ok(Box::new(f.data.iter().map(|t| Ok(Tuple(t.clone()))))),
-            RelAlgebra::Triple(r) => r.iter(tx),
+            RelAlgebra::Triple(r) => r.iter(tx, epoch),
             RelAlgebra::Derived(r) => r.iter(epoch, use_delta),
             RelAlgebra::Relation(v) => v.iter(tx),
             RelAlgebra::Join(j) => j.iter(tx, epoch, use_delta),
@@ -1893,6 +3195,7 @@ impl NegJoin {
                     join_indices,
                     tx,
                     eliminate_indices,
+                    epoch,
                 )
             }
             RelAlgebra::Derived(r) => {
@@ -1978,6 +3281,12 @@ impl InnerJoin {
     ) -> Result<TupleIter<'a>> {
         let bindings = self.bindings();
         let eliminate_indices = get_eliminate_indices(&bindings, &self.to_eliminate);
+        if !self.joiner.left_keys.is_empty()
+            && self.left.is_sorted_on(&self.joiner.left_keys)
+            && self.right.is_sorted_on(&self.joiner.right_keys)
+        {
+            return self.merge_join(tx, eliminate_indices, epoch, use_delta);
+        }
         match &self.right {
             RelAlgebra::Fixed(f) => {
                 let join_indices = self
@@ -2006,6 +3315,8 @@ impl InnerJoin {
                     join_indices,
                     tx,
                     eliminate_indices,
+                    epoch,
+                    self.left.bindings_after_eliminate().len(),
                 )
             }
             RelAlgebra::Derived(r) => {
@@ -2024,6 +3335,10 @@ impl InnerJoin {
                         epoch,
                         use_delta,
                     )
+                } else if self.left.is_cheap_to_materialize()
+                    || self.right.is_cheap_to_materialize()
+                {
+                    self.hash_join(tx, eliminate_indices, epoch, use_delta)
                 } else {
                     self.materialized_join(tx, eliminate_indices, epoch, use_delta)
                 }
@@ -2048,7 +3363,11 @@ impl InnerJoin {
                 }
             }
             RelAlgebra::Join(_) | RelAlgebra::Filter(_) | RelAlgebra::Unification(_) => {
-                self.materialized_join(tx, eliminate_indices, epoch, use_delta)
+                if self.left.is_cheap_to_materialize() || self.right.is_cheap_to_materialize() {
+                    self.hash_join(tx, eliminate_indices, epoch, use_delta)
+                } else {
+                    self.materialized_join(tx, eliminate_indices, epoch, use_delta)
+                }
             }
             RelAlgebra::Reorder(_) => {
                 panic!("joining on reordered")
@@ -2058,6 +3377,110 @@ impl InnerJoin {
             }
         }
     }
+    fn merge_join<'a>(
+        &'a self,
+        tx: &'a SessionTx,
+        eliminate_indices: BTreeSet<usize>,
+        epoch: Option<u32>,
+        use_delta: &BTreeSet<DerivedRelStoreId>,
+    ) -> Result<TupleIter<'a>> {
+        let (left_join_indices, right_join_indices) = self.joiner.join_indices(
+            &self.left.bindings_after_eliminate(),
+            &self.right.bindings_after_eliminate(),
+        )?;
+        Ok(Box::new(MergeJoinIter {
+            left: self.left.iter(tx, epoch, use_delta)?.peekable(),
+            right: self.right.iter(tx, epoch, use_delta)?.peekable(),
+            left_join_indices,
+            right_join_indices,
+            eliminate_indices,
+            buffer: Default::default(),
+        }))
+    }
+    /// A symmetric hash join used when neither side admits a prefix join but at least one side
+    /// is [`RelAlgebra::is_cheap_to_materialize`] -- i.e. already a bounded, computed relation
+    /// rather than a live base-relation scan. The cheaper-looking side (preferring `right` on a
+    /// tie, to match [`InnerJoin::materialized_join`]'s existing build side) is hashed fully into
+    /// memory by its join columns; the other side is streamed and probed against it.
+    fn hash_join<'a>(
+        &'a self,
+        tx: &'a SessionTx,
+        eliminate_indices: BTreeSet<usize>,
+        epoch: Option<u32>,
+        use_delta: &BTreeSet<DerivedRelStoreId>,
+    ) -> Result<TupleIter<'a>> {
+        let (left_join_indices, right_join_indices) = self.joiner.join_indices(
+            &self.left.bindings_after_eliminate(),
+            &self.right.bindings_after_eliminate(),
+        )?;
+        let build_left = self.left.is_cheap_to_materialize() && !self.right.is_cheap_to_materialize();
+        if build_left {
+            let mut built: HashMap<Box<[DataValue]>, Vec<Tuple>> = HashMap::new();
+            for item in self.left.iter(tx, epoch, use_delta)? {
+                let tuple = item?;
+                let key: Box<[DataValue]> = left_join_indices
+                    .iter()
+                    .map(|i| tuple.0[*i].clone())
+                    .collect();
+                built.entry(key).or_default().push(tuple);
+            }
+            Ok(Box::new(
+                self.right
+                    .iter(tx, epoch, use_delta)?
+                    .map_ok(move |right_tuple| {
+                        let eliminate_indices = eliminate_indices.clone();
+                        let key: Box<[DataValue]> = right_join_indices
+                            .iter()
+                            .map(|i| right_tuple.0[*i].clone())
+                            .collect();
+                        let matches = built.get(&key).cloned().unwrap_or_default();
+                        matches.into_iter().map(move |left_tuple| {
+                            let mut ret = left_tuple.0;
+                            ret.extend(right_tuple.0.iter().cloned());
+                            Ok(eliminate_from_tuple(Tuple(ret), &eliminate_indices))
+                        })
+                    })
+                    .flatten_ok()
+                    .map(flatten_err),
+            ))
+        } else {
+            let mut built: HashMap<Box<[DataValue]>, Vec<Tuple>> = HashMap::new();
+            for item in self.right.iter(tx, epoch, use_delta)? {
+                let tuple = item?;
+                let key: Box<[DataValue]> = right_join_indices
+                    .iter()
+                    .map(|i| tuple.0[*i].clone())
+                    .collect();
+                built.entry(key).or_default().push(tuple);
+            }
+            Ok(Box::new(
+                self.left
+                    .iter(tx, epoch, use_delta)?
+                    .map_ok(move |left_tuple| {
+                        let eliminate_indices = eliminate_indices.clone();
+                        let key: Box<[DataValue]> = left_join_indices
+                            .iter()
+                            .map(|i| left_tuple.0[*i].clone())
+                            .collect();
+                        let matches = built.get(&key).cloned().unwrap_or_default();
+                        matches.into_iter().map(move |right_tuple| {
+                            let mut ret = left_tuple.0.clone();
+                            ret.extend(right_tuple.0.iter().cloned());
+                            Ok(eliminate_from_tuple(Tuple(ret), &eliminate_indices))
+                        })
+                    })
+                    .flatten_ok()
+                    .map(flatten_err),
+            ))
+        }
+    }
+    /// Builds an in-memory `HashMap` from the right side's join-column values to the whole right
+    /// tuples having them (join columns included, not split off), up to
+    /// [`DEFAULT_JOIN_SPILL_THRESHOLD`] rows; if the right side turns
+    /// out to be bigger than that, the rows buffered so far (plus the rest of the right iterator)
+    /// are drained into a throwaway temp store instead, exactly like the pre-existing
+    /// disk-backed path, so only right sides cheap enough to fit in memory skip RocksDB
+    /// encode/decode and disk I/O.
     fn materialized_join<'a>(
         &'a self,
         tx: &'a SessionTx,
@@ -2071,7 +3494,7 @@ impl InnerJoin {
             .join_indices(&self.left.bindings_after_eliminate(), &right_bindings)
             .unwrap();
         let right_join_indices_set = BTreeSet::from_iter(right_join_indices.iter().cloned());
-        let mut right_store_indices = right_join_indices;
+        let mut right_store_indices = right_join_indices.clone();
         for i in 0..right_bindings.len() {
             if !right_join_indices_set.contains(&i) {
                 right_store_indices.push(i)
@@ -2083,8 +3506,60 @@ impl InnerJoin {
             .sorted_by_key(|(_, b)| **b)
             .map(|(a, _)| a)
             .collect_vec();
+
+        let mut right_source = self.right.iter(tx, epoch, use_delta)?;
+        let mut hash_table: HashMap<Box<[DataValue]>, Vec<Tuple>> = HashMap::new();
+        let mut spilled = false;
+        let mut row_count = 0usize;
+        for item in right_source.by_ref().take(DEFAULT_JOIN_SPILL_THRESHOLD + 1) {
+            let tuple = item?;
+            let key: Box<[DataValue]> = right_join_indices
+                .iter()
+                .map(|i| tuple.0[*i].clone())
+                .collect();
+            hash_table.entry(key).or_default().push(tuple);
+            row_count += 1;
+            if row_count > DEFAULT_JOIN_SPILL_THRESHOLD {
+                spilled = true;
+                break;
+            }
+        }
+
+        if !spilled {
+            return Ok(Box::new(
+                self.left
+                    .iter(tx, epoch, use_delta)?
+                    .map_ok(move |tuple| {
+                        let eliminate_indices = eliminate_indices.clone();
+                        let key: Box<[DataValue]> = left_join_indices
+                            .iter()
+                            .map(|i| tuple.0[*i].clone())
+                            .collect();
+                        let matches = hash_table.get(&key).cloned().unwrap_or_default();
+                        matches.into_iter().map(move |found| {
+                            let mut ret = tuple.0.clone();
+                            ret.extend(found.0);
+                            Ok(eliminate_from_tuple(Tuple(ret), &eliminate_indices))
+                        })
+                    })
+                    .flatten_ok()
+                    .map(flatten_err),
+            ));
+        }
+
         let throwaway = tx.new_temp_store(SourceSpan(0, 0));
-        for item in self.right.iter(tx, epoch, use_delta)? {
+        for tuples in hash_table.into_values() {
+            for tuple in tuples {
+                let stored_tuple = Tuple(
+                    right_store_indices
+                        .iter()
+                        .map(|i| tuple.0[*i].clone())
+                        .collect_vec(),
+                );
+                throwaway.put(stored_tuple, 0);
+            }
+        }
+        for item in right_source {
             match item {
                 Ok(tuple) => {
                     let stored_tuple = Tuple(